@@ -1,81 +1,80 @@
+use crate::calendar::{self, Calendar, Status};
+use crate::config::Config;
+use crate::download::{self, Request, Response, Worker};
+use crate::games::{self, Arrow, Game, GAMES};
+use crate::practice;
+use crate::prefetch::{Background, Report};
+use crate::puzzle::PuzzleError;
+use crate::stats::{self, Record};
 use crate::tabs::Tabber;
-use chrono::{DateTime, Duration, Local};
-use rand::seq::SliceRandom;
+use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDate, TimeZone};
 use ratatui::{
-    layout::{Alignment, Constraint, Layout},
+    layout::{Alignment, Constraint, Layout, Rect},
     style::{Style, Stylize},
-    text::{Line, Text},
+    text::Text,
     widgets::{Block, Paragraph, Tabs},
     Frame,
 };
-use GameResult::*;
+use std::collections::BTreeMap;
+use std::time::Instant;
 
-#[derive(PartialEq, Debug, Clone)]
-enum GameResult {
-    Green,
-    Yellow,
-    Grey,
-}
+const SPINNER: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
 
-static WORDS: &str = include_str!("../assets/wordle.txt");
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct App {
+    pub page: Tabber,      // keeps track of which page we are on
+    pub should_quit: bool, // determines whether to quit or not
+    pub game_cache: Vec<(u8, DateTime<Local>, String)>, // Vector of game cached data
+    pub date: DateTime<Local>, // date to check games on
 
-fn calc_game(correct: &str, guess: &str) -> [GameResult; 5] {
-    const ARRAY_REPEAT_VALUE: GameResult = GameResult::Grey;
+    #[serde(skip)]
+    pub game: Option<Box<dyn Game>>, // The current game being played
 
-    let mut res = [ARRAY_REPEAT_VALUE; 5];
+    pub progress: BTreeMap<String, serde_json::Value>, // saved progress, by `progress_key`
 
-    let correct_chars: Vec<char> = correct.chars().collect();
-    let guess_chars: Vec<char> = guess.chars().collect();
+    #[serde(skip)]
+    pub game_key: Option<String>, // progress key of the current game
 
-    let mut correct_count = [0; 26]; // assuming only lowercase letters
-    let mut guess_count = [0; 26];
+    #[serde(skip)]
+    pub error: Option<String>, // shown instead of the game when it could not be loaded
 
-    // First pass: identify all Green matches
-    for i in 0..5 {
-        if correct_chars[i] == guess_chars[i] {
-            res[i] = Green;
-        } else {
-            correct_count[correct_chars[i] as usize - 'a' as usize] += 1;
-            guess_count[guess_chars[i] as usize - 'a' as usize] += 1;
-        }
-    }
+    #[serde(skip)]
+    pub game_area: Rect, // where the game was last drawn, for mouse clicks
 
-    // Second pass: identify Yellow matches
-    for i in 0..5 {
-        if res[i] != Green && correct_count[guess_chars[i] as usize - 'a' as usize] > 0 {
-            res[i] = Yellow;
-            correct_count[guess_chars[i] as usize - 'a' as usize] -= 1;
-        }
-    }
+    pub practice: bool,     // play local puzzles instead of the NYT's
+    pub practice_seed: u64, // picks the practice puzzles, unless the config sets a seed
 
-    res
-}
+    #[serde(skip)]
+    pub notice: Option<String>, // shown under the game
 
-#[derive(serde::Serialize, serde::Deserialize)]
-#[serde(default)]
-pub struct App {
-    pub page: Tabber,      // keeps track of which page we are on
-    pub should_quit: bool, // determines whether to quit or not
-    pub game_cache: Vec<(u8, DateTime<Local>, String)>, // Vector of game cached data
-    pub date: DateTime<Local>, // date to check games on
-    pub current_game: (u8, serde_json::Value), // The current game being played
-    pub guess_buffer: Vec<char>, // The guess
-    pub guesses: Vec<String>, // Guesses are stored here
-    pub lines: Vec<String>,
+    #[serde(skip)]
+    pub config: Config,
+
+    pub stats: Vec<Record>, // every finished daily game
+
+    pub play_time: BTreeMap<String, u64>, // seconds spent on each game, by `progress_key`
 
     #[serde(skip)]
-    pub words: Vec<String>,
+    pub started: Option<Instant>, // when time on the current game was last counted
 
-    pub game_string: Vec<String>,
+    #[serde(skip)]
+    pub shared: Vec<String>, // results shared this session, written out on exit
+
+    #[serde(skip)]
+    pub worker: Option<Worker>, // downloads puzzles in the background, started when needed
 
-    pub game_complete: bool,
+    #[serde(skip)]
+    pub loading: Option<(u64, Instant)>, // id of the download being waited on, and when it began
 
-    pub word_order: Vec<String>, // for connections
+    #[serde(skip)]
+    pub prefetching: Option<Background>, // puzzles being cached for playing offline
 
-    pub used_words: Vec<String>,
+    #[serde(skip)]
+    pub today: NaiveDate, // today's date when it was last checked, to notice midnight
 
-    pub found_words: Vec<String>,
-    pub needed_words: u8,
+    #[serde(skip)]
+    pub calendar: Option<Calendar>, // shown instead of the game while picking a date
 }
 
 impl Default for App {
@@ -83,473 +82,701 @@ impl Default for App {
         Self {
             page: Tabber {
                 index: 0,
-                values: vec![
-                    "Wordle".to_string(),
-                    "Connections".to_string(),
-                    "Strands".to_string(),
-                ],
+                values: tab_names(),
             },
             should_quit: false,
             game_cache: Vec::new(),
             date: Local::now(),
-            current_game: (255, serde_json::json!({})),
-            guess_buffer: Vec::new(),
-            guesses: Vec::new(),
-            lines: Vec::new(),
+            game: None,
+            progress: BTreeMap::new(),
+            game_key: None,
+            error: None,
+            game_area: Rect::default(),
+            practice: false,
+            practice_seed: rand::random(),
+            notice: None,
+            config: Config::default(),
+            stats: Vec::new(),
+            play_time: BTreeMap::new(),
+            started: None,
+            shared: Vec::new(),
+            worker: None,
+            loading: None,
+            prefetching: None,
+            today: Local::now().date_naive(),
+            calendar: None,
+        }
+    }
+}
 
-            words: WORDS.split('\n').map(|x| x.trim().to_string()).collect(),
+/// A date as the app keeps it. Noon, so it can't fall into a daylight saving gap.
+pub fn local_date(date: NaiveDate) -> DateTime<Local> {
+    date.and_hms_opt(12, 0, 0)
+        .and_then(|d| Local.from_local_datetime(&d).earliest())
+        .unwrap_or_else(Local::now)
+}
 
-            game_string: Vec::new(),
+/// The registered games, followed by the stats page
+pub fn tab_names() -> Vec<String> {
+    let mut names = games::names();
 
-            game_complete: false,
+    names.push("Stats".to_string());
 
-            word_order: Vec::new(),
-            used_words: Vec::new(),
+    names
+}
 
-            found_words: Vec::new(),
-            needed_words: 0,
+impl App {
+    /// The current game, unless it has already been completed
+    fn playing(&mut self) -> Option<&mut Box<dyn Game>> {
+        if self.calendar.is_some() {
+            return None; // the calendar covers the game
         }
+
+        self.game.as_mut().filter(|game| !game.is_complete())
     }
-}
 
-impl App {
-    pub fn generate_game_string(&mut self) {
-        match self.page.index {
-            0 => {
-                self.game_string.append(&mut vec![
-                    "Wordle: Guess a five letter word to win the game.".into(),
-                    "".to_string(),
-                ]);
-            }
+    /// Passes some input to the current game, and records it in the stats if it completes it
+    fn play(&mut self, action: impl FnOnce(&mut Box<dyn Game>)) {
+        let Some(game) = self.playing() else {
+            return;
+        };
 
-            1 => {
-                self.game_string.append(&mut vec![
-                    "Connections: Group words by a common thread.".into(),
-                    "".to_string(),
-                ]);
+        action(game);
 
-                let mut words: Vec<String> = Vec::new();
+        if game.is_complete() {
+            self.record();
+        }
+    }
 
-                let temp_vec = Vec::new();
+    /// Adds the just completed game to the stats
+    fn record(&mut self) {
+        self.track_time();
 
-                let categories = self.current_game.1["categories"]
-                    .as_array()
-                    .unwrap_or(&temp_vec);
+        let (Some(game), Some(key)) = (&self.game, &self.game_key) else {
+            return;
+        };
 
-                for cat in categories {
-                    let cards = cat["cards"].as_array().unwrap_or(&temp_vec);
+        let (Some(outcome), false) = (game.outcome(), self.practice) else {
+            return;
+        };
 
-                    for card in cards {
-                        let card = card.as_object();
+        let endpoint = GAMES[self.page.index as usize].endpoint;
+        let date = self.date.date_naive();
 
-                        if let Some(c) = card {
-                            words.push(c["content"].as_str().unwrap_or_default().to_string());
-                        }
-                    }
-                }
+        if self
+            .stats
+            .iter()
+            .any(|r| r.game == endpoint && r.date == date)
+        {
+            return;
+        }
 
-                words.shuffle(&mut rand::thread_rng());
+        let seconds = self.play_time.get(key).copied().unwrap_or(0);
+        let number = game.metadata().number;
 
-                self.word_order = words.clone();
+        self.stats
+            .push(Record::new(endpoint, date, number, outcome, seconds));
+    }
 
-                let letters = [
-                    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
-                    'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
-                ];
+    /// Adds the time since it was last counted to the current game's play time
+    fn track_time(&mut self) {
+        if let (Some(started), Some(key)) = (self.started, &self.game_key) {
+            *self.play_time.entry(key.clone()).or_default() += started.elapsed().as_secs();
+        }
 
-                for (i, word) in words.into_iter().enumerate() {
-                    self.game_string.push(format!("{}. {}", letters[i], word));
-                }
+        self.restart_clock();
+    }
 
-                self.game_string.push("".into());
-            }
+    /// Starts counting time on the current game, if it is still being played
+    fn restart_clock(&mut self) {
+        self.started = self
+            .game
+            .as_ref()
+            .filter(|game| !game.is_complete())
+            .map(|_| Instant::now());
+    }
 
-            2 => {
-                self.game_string.push("Strands: Uncover words.".to_string());
+    pub fn on_stats(&self) -> bool {
+        self.page.index as usize == GAMES.len()
+    }
 
-                self.game_string.push(format!(
-                    "Clue: {}",
-                    self.current_game.1["clue"].as_str().unwrap_or_default()
-                ));
+    pub fn key(&mut self, char: char) {
+        self.play(|game| game.key(char));
+    }
 
-                // generate self.needed_words
+    pub fn backspace(&mut self) {
+        self.play(|game| game.backspace());
+    }
 
-                self.needed_words = (self.current_game.1["themeWords"]
-                    .as_array()
-                    .unwrap_or(&Vec::new())
-                    .len()
-                    + 1) as u8;
+    pub fn enter(&mut self) {
+        if let Some(calendar) = self.calendar.take() {
+            self.date = local_date(calendar.cursor);
 
-                self.game_string
-                    .push(format!("Theme words: {}", self.needed_words));
+            self.load_game();
 
-                self.game_string.push("".into());
+            return;
+        }
 
-                let null = Vec::new();
+        self.play(|game| game.enter());
+    }
 
-                let starting_board = self.current_game.1["startingBoard"]
-                    .as_array()
-                    .unwrap_or(&null);
+    /// Passes an arrow key to the calendar or the game, falling back to changing the tab or date
+    pub fn arrow(&mut self, arrow: Arrow) {
+        if let Some(calendar) = &self.calendar {
+            let cursor = calendar.step_days(match arrow {
+                Arrow::Left => -1,
+                Arrow::Right => 1,
+                Arrow::Up => -7,
+                Arrow::Down => 7,
+            });
 
-                for line in starting_board {
-                    self.game_string
-                        .push(line.as_str().unwrap_or_default().to_string());
-                }
+            self.show_calendar(cursor);
+
+            return;
+        }
 
-                self.game_string.push("".to_string());
+        if let Some(game) = self.playing() {
+            if game.arrow(arrow) {
+                return;
             }
+        }
 
-            _ => {}
+        match arrow {
+            Arrow::Left => self.left(),
+            Arrow::Right => self.right(),
+            Arrow::Up => self.up(),
+            Arrow::Down => self.down(),
         }
     }
 
-    pub fn key(&mut self, char: char) {
-        let max = match self.page.index {
-            0 => 5,
-            1 => 4,
-            2 => 20,
-            _ => 1,
-        };
+    pub fn click(&mut self, column: u16, row: u16) {
+        let area = self.game_area;
 
-        if self.guess_buffer.len() != max {
-            self.guess_buffer.push(char);
-        }
+        self.play(|game| game.click(area, column, row));
     }
 
-    pub fn clear_state(&mut self) {
-        self.guess_buffer.clear();
-        self.guesses.clear();
-        self.lines.clear();
-        self.game_string.clear();
-        self.used_words.clear();
-        self.word_order.clear();
-        self.found_words.clear();
-        self.needed_words = 0;
-    }
+    /// The puzzle data for the current tab and date, if it doesn't need downloading
+    fn local(&self, date: NaiveDate) -> Option<Result<String, PuzzleError>> {
+        if self.practice {
+            let info = &GAMES[self.page.index as usize];
 
-    pub fn download(&mut self) -> Result<(u8, serde_json::Value), ()> {
-        for game in &self.game_cache {
-            if self.page.index == game.0 && self.date.date_naive() == game.1.date_naive() {
-                return Ok((game.0, serde_json::from_str(&game.2).unwrap_or_default()));
-            }
+            return Some(practice::puzzle(
+                info,
+                &self.config.practice,
+                self.date_seed(date),
+            ));
         }
 
-        let base_url = match self.page.index {
-            0 => "https://www.nytimes.com/svc/wordle/v2/",
-            1 => "https://www.nytimes.com/svc/connections/v2/",
-            2 => "https://www.nytimes.com/svc/strands/v2/",
-            _ => "",
-        };
-
-        let data = reqwest::blocking::get(format!("{}{}.json", base_url, self.date.date_naive()));
+        self.game_cache
+            .iter()
+            .find(|game| self.page.index == game.0 && date == game.1.date_naive())
+            .map(|game| Ok(game.2.clone()))
+    }
 
-        if let Ok(d) = data {
-            if let Ok(json) = d.json::<serde_json::Value>() {
-                if json["status"].as_str().unwrap_or("OK") == "ERROR" {
-                    return Err(());
-                }
-                self.game_cache
-                    .push((self.page.index, self.date, json.to_string()));
+    /// The dates of a game's first and latest puzzles
+    fn date_range(&self, game: u8) -> (NaiveDate, NaiveDate) {
+        (GAMES[game as usize].first_date, self.config.today.today())
+    }
 
-                Ok((self.page.index, json))
-            } else {
-                self.lines
-                    .push("Failed to convert data to JSON".to_string());
+    /// Checks that the current game has a puzzle on the current date
+    fn check_date(&self) -> Result<(), PuzzleError> {
+        let (first, latest) = self.date_range(self.page.index);
 
-                Err(())
-            }
+        if (first..=latest).contains(&self.date.date_naive()) {
+            Ok(())
         } else {
-            self.lines.push("Failed to download game".to_string());
-
-            Err(())
+            Err(PuzzleError::OutOfRange(first, latest))
         }
     }
-    pub fn left(&mut self) {
-        self.page.prev();
 
-        self.clear_state();
+    /// Where the puzzle for the current tab and date is downloaded from
+    fn url(&self) -> String {
+        self.url_for(self.page.index, self.date.date_naive())
+    }
 
-        if let Ok(data) = self.download() {
-            self.current_game = data;
-        }
+    fn url_for(&self, game: u8, date: NaiveDate) -> String {
+        format!(
+            "{}/{}/v2/{}.json",
+            self.config.api.base_url(),
+            GAMES[game as usize].endpoint,
+            date
+        )
+    }
+
+    fn is_cached(&self, game: u8, date: NaiveDate) -> bool {
+        self.game_cache
+            .iter()
+            .any(|c| c.0 == game && c.1.date_naive() == date)
+    }
 
-        self.game_complete = false;
+    /// Caches downloaded data, but only if it makes a puzzle, so a bad download is retried
+    /// next time
+    fn cache(
+        &mut self,
+        game: u8,
+        date: DateTime<Local>,
+        data: String,
+    ) -> Result<String, PuzzleError> {
+        (GAMES[game as usize].load)(&data)?;
+
+        if !self.is_cached(game, date.date_naive()) {
+            self.game_cache.push((game, date, data.clone()));
+        }
 
-        self.generate_game_string();
+        Ok(data)
     }
 
-    pub fn right(&mut self) {
-        self.page.next();
+    /// The raw puzzle data for the current tab and date, from the cache if possible. Blocks
+    /// while downloading, unlike `load_game`.
+    pub fn fetch(&mut self) -> Result<String, PuzzleError> {
+        self.check_date()?;
+
+        if let Some(result) = self.local(self.date.date_naive()) {
+            return result;
+        }
 
-        self.clear_state();
+        let client = download::client(self.config.api.timeout());
+        let data = download::get(&client, &self.url())?;
 
-        if let Ok(data) = self.download() {
-            self.current_game = data;
+        self.cache(self.page.index, self.date, data)
+    }
+
+    /// The downloads needed to cache every game's puzzles from `from` to `to`, and a report
+    /// to fill in as they finish
+    pub fn plan_prefetch(&self, from: NaiveDate, to: NaiveDate) -> (Vec<Request>, Report) {
+        let mut requests = Vec::new();
+        let mut outside = Vec::new();
+        let mut total = 0;
+
+        for date in from.iter_days().take_while(|d| *d <= to) {
+            for game in 0..GAMES.len() as u8 {
+                let (first, latest) = self.date_range(game);
+
+                total += 1;
+
+                // outside the archive, so there is nothing to download
+                if date < first || date > latest {
+                    outside.push((date, game));
+                } else if !self.is_cached(game, date) {
+                    requests.push(Request {
+                        id: requests.len() as u64,
+                        game,
+                        date: local_date(date),
+                        url: self.url_for(game, date),
+                    });
+                }
+            }
         }
 
-        self.game_complete = false;
+        let report = Report::new(total, requests.len(), outside);
 
-        self.generate_game_string();
+        (requests, report)
     }
 
-    pub fn up(&mut self) {
-        // increment the date by one
+    /// Caches a prefetched puzzle, and adds it to the report
+    pub fn prefetched(&mut self, response: Response, report: &mut Report) {
+        let Response { request, result } = response;
 
-        self.date += Duration::days(1);
+        let result = result
+            .and_then(|data| self.cache(request.game, request.date, data))
+            .map(|_| ());
 
-        self.clear_state();
+        report.add(&request, result);
+    }
 
-        if let Ok(data) = self.download() {
-            self.current_game = data;
+    /// Starts caching the puzzles of the days up to the selected date in the background
+    pub fn prefetch(&mut self) {
+        if self.prefetching.is_some() {
+            return;
         }
 
-        self.game_complete = false;
+        let to = self.date.date_naive();
 
-        self.generate_game_string();
-    }
+        // no game has puzzles before the earliest archive, so a long range stops there
+        let earliest = GAMES.iter().map(|g| g.first_date).min().unwrap_or(to);
+        let from = to
+            .checked_sub_days(Days::new(self.config.prefetch.days() - 1))
+            .unwrap_or(earliest)
+            .max(earliest);
 
-    pub fn down(&mut self) {
-        // decrement the date
+        let (requests, report) = self.plan_prefetch(from, to);
+
+        self.notice = Some(report.summary());
 
-        self.date -= Duration::days(1);
+        self.prefetching = Some(Background::start(
+            requests,
+            report,
+            self.config.api.timeout(),
+            self.config.prefetch.delay(),
+        ));
+    }
 
-        self.clear_state();
+    /// Makes the game for the current tab and date from its data, with any saved progress on it
+    fn with_progress(&self, data: &str, date: NaiveDate) -> Result<Box<dyn Game>, PuzzleError> {
+        let mut game = (GAMES[self.page.index as usize].load)(data)?;
 
-        if let Ok(data) = self.download() {
-            self.current_game = data;
+        if let Some(state) = self.progress.get(&self.progress_key(date)) {
+            // progress from an incompatible version is dropped
+            let _ = game.restore(state.clone());
         }
 
-        self.game_complete = false;
+        Ok(game)
+    }
 
-        self.generate_game_string();
+    /// Downloads the game for the current tab and date, with any saved progress on it
+    pub fn open(&mut self) -> Result<Box<dyn Game>, PuzzleError> {
+        let data = self.fetch()?;
+
+        self.with_progress(&data, self.date.date_naive())
     }
 
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    /// The seed of the practice puzzle for the current date. Each date has its own practice
+    /// puzzle, so they can be stepped through like the daily ones.
+    fn date_seed(&self, date: NaiveDate) -> u64 {
+        self.config
+            .practice
+            .seed
+            .unwrap_or(self.practice_seed)
+            .wrapping_add(date.num_days_from_ce() as u64)
     }
 
-    pub fn enter(&mut self) {
-        let max = match self.page.index {
-            0 => 5,
-            1 => 4,
-            2 => 20,
-            _ => 1,
-        };
+    /// Identifies the puzzle for the current tab and date in `progress`
+    fn progress_key(&self, date: NaiveDate) -> String {
+        let game = GAMES[self.page.index as usize].endpoint;
 
-        match self.page.index {
-            0 | 1 => {
-                if self.guess_buffer.len() != max {
-                    return;
-                }
-            }
-            2 => {
-                if self.guess_buffer.len() < 4 {
-                    return;
-                }
-            }
-            _ => (),
+        if self.practice {
+            format!("practice/{}/{}", self.date_seed(date), game)
+        } else {
+            format!("{}/{}", game, date)
         }
+    }
 
-        self.guesses.push(
-            self.guess_buffer
-                .iter()
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>()
-                .join(""),
-        );
+    /// Saves the progress on the current game into `progress`
+    pub fn store_progress(&mut self) {
+        self.track_time();
 
-        // game logic
+        if let (Some(game), Some(key)) = (&self.game, &self.game_key) {
+            self.progress.insert(key.clone(), game.save());
+        }
+    }
 
-        match self.page.index {
-            0 => {
-                // wordle
+    /// Loads the game for the current tab and date, and restores any progress on it. The
+    /// progress on the game being left is kept. Puzzles that aren't cached are downloaded in the
+    /// background, and shown by `poll` once they arrive.
+    pub fn load_game(&mut self) {
+        self.store_progress();
 
-                if !self.words.contains(self.guesses.last().unwrap()) {
-                    return;
-                }
+        // the calendar follows the game and the mode
+        if let Some(calendar) = self.calendar.take() {
+            if !self.on_stats() {
+                self.show_calendar(calendar.cursor);
             }
+        }
 
-            1 => {
-                // connections
+        // anything still downloading is for the game being left
+        self.loading = None;
 
-                let guess = self.guesses.last().unwrap();
+        self.game = None;
+        self.game_key = None;
+        self.error = None;
 
-                let chars = guess.chars().collect::<Vec<_>>();
+        if self.on_stats() {
+            return;
+        }
 
-                let mut dedup_chars = chars.clone();
+        if let Err(e) = self.check_date() {
+            self.error = Some(e.to_string());
 
-                dedup_chars.dedup();
+            return;
+        }
 
-                if dedup_chars != chars {
-                    return;
-                }
+        match self.local(self.date.date_naive()) {
+            Some(result) => self.show(result),
+            None => {
+                let (game, date, url) = (self.page.index, self.date, self.url());
+                let timeout = self.config.api.timeout();
+
+                let id = self
+                    .worker
+                    .get_or_insert_with(|| Worker::start(timeout))
+                    .send(game, date, url);
+
+                self.loading = Some((id, Instant::now()));
             }
+        }
+    }
 
-            _ => {}
+    /// Shows the puzzle for the current tab and date, or why it couldn't be fetched
+    fn show(&mut self, result: Result<String, PuzzleError>) {
+        match result.and_then(|data| self.with_progress(&data, self.date.date_naive())) {
+            Ok(game) => {
+                self.game = Some(game);
+                self.game_key = Some(self.progress_key(self.date.date_naive()));
+            }
+            // possibly offline, so offer the practice puzzles rather than switching to them
+            Err(e @ PuzzleError::Network(_)) => {
+                self.error = Some(format!("{}\nctrl-p: play practice puzzles offline", e))
+            }
+            Err(e) => self.error = Some(e.to_string()),
         }
 
-        // now push it to lines
+        self.restart_clock();
+    }
+
+    /// Whether the saved puzzle was finished. Played puzzles are always cached, so one that
+    /// isn't hasn't been started.
+    fn saved_finished(&self) -> bool {
+        if self.on_stats() {
+            return true;
+        }
+
+        match self.local(self.date.date_naive()) {
+            Some(Ok(data)) => self
+                .with_progress(&data, self.date.date_naive())
+                .is_ok_and(|game| game.is_complete()),
+            _ => true,
+        }
+    }
+
+    /// Moves on from a finished saved puzzle to today's, unless the config turns this off.
+    /// Called on startup, before the game is loaded.
+    pub fn follow_today(&mut self) {
+        self.today = self.config.today.today();
 
-        match self.page.index {
-            0 => {
-                // color the grid
+        if self.config.today.follow()
+            && self.date.date_naive() != self.today
+            && self.saved_finished()
+        {
+            self.date = local_date(self.today);
+        }
+    }
 
-                let guess = self.guesses.last().unwrap();
-                let correct = self.current_game.1["solution"].as_str().unwrap_or("crane");
+    /// Offers the new puzzle when midnight passes while playing the day's puzzle
+    fn check_day(&mut self) {
+        let today = self.config.today.today();
 
-                let result = calc_game(correct, guess)
-                    .iter()
-                    .map(|x| match x {
-                        Green => "\x1b[32mG\x1b[0m",
-                        Yellow => "\x1b[33mY\x1b[0m",
-                        Grey => "\x1b[90mN\x1b[0m",
-                    })
-                    .collect::<Vec<_>>();
+        if today == self.today {
+            return;
+        }
 
-                self.lines.push(format!("{}, {}", guess, result.join("")));
+        if self.date.date_naive() == self.today {
+            self.notice = Some("A new puzzle is out, ctrl-t: open today's puzzle".to_string());
+        }
 
-                if guess == correct {
-                    // correct guess :D
+        self.today = today;
+    }
 
-                    self.lines.push("Game complete!".to_string());
+    pub fn go_today(&mut self) {
+        self.date = local_date(self.config.today.today());
+        self.notice = None;
 
-                    self.game_complete = true;
-                }
+        self.load_game();
+    }
+
+    /// Handles downloads finished in the background. Stale ones are only cached.
+    /// Called on every tick of the draw loop.
+    pub fn poll(&mut self) {
+        self.check_day();
+
+        if let Some(mut prefetch) = self.prefetching.take() {
+            for response in prefetch.finished() {
+                self.prefetched(response, &mut prefetch.report);
             }
-            1 => {
-                // push the guess into the lines vector
 
-                let letters = [
-                    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
-                    'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
-                ];
+            self.notice = Some(prefetch.report.summary());
 
-                let guess = self.guesses.last().unwrap();
+            if prefetch.report.remaining() > 0 {
+                self.prefetching = Some(prefetch);
+            }
+        }
 
-                let mut words: Vec<&str> = Vec::new();
+        let Some(worker) = &self.worker else {
+            return;
+        };
 
-                let unknown_str = "Unknown".to_string();
+        for response in worker.finished() {
+            let Response { request, result } = response;
 
-                for ch in guess.chars() {
-                    let word = self
-                        .word_order
-                        .get(letters.iter().position(|&x| x == ch).unwrap_or(1000))
-                        .unwrap_or(&unknown_str)
-                        .as_str();
+            let result = result.and_then(|data| self.cache(request.game, request.date, data));
 
-                    if self.used_words.contains(&word.to_string()) {
-                        return;
-                    }
+            if self.loading.is_some_and(|(id, _)| id == request.id) {
+                self.loading = None;
 
-                    words.push(word);
-                }
+                self.show(result);
+            }
+        }
+    }
 
-                let mut status = 0; // 0 => Incorrect, 1 => One Away, 2 => Correct
+    /// Opens the calendar of the current game's archive, or closes it
+    pub fn toggle_calendar(&mut self) {
+        if self.calendar.take().is_none() && !self.on_stats() {
+            self.show_calendar(self.date.date_naive());
+        }
+    }
 
-                let temp_vec = Vec::new();
+    /// Shows the calendar's month around `cursor`, with how each day's puzzle went
+    fn show_calendar(&mut self, cursor: NaiveDate) {
+        // so the current game's status is up to date
+        self.store_progress();
+
+        let (first, latest) = self.date_range(self.page.index);
+        let cursor = cursor.clamp(first, latest);
+
+        let statuses = calendar::month(cursor)
+            .filter(|day| (first..=latest).contains(day))
+            .map(|day| (day, self.status(day)))
+            .collect();
+
+        self.calendar = Some(Calendar {
+            cursor,
+            first,
+            latest,
+            statuses,
+        });
+    }
 
-                for cat in self.current_game.1["categories"]
-                    .as_array()
-                    .unwrap_or(&temp_vec)
-                {
-                    let mut correct = 0;
+    /// How the current game's puzzle on `date` went, from the stats or the saved progress
+    fn status(&self, date: NaiveDate) -> Status {
+        let endpoint = GAMES[self.page.index as usize].endpoint;
 
-                    let cards = cat["cards"].as_array().unwrap_or(&temp_vec);
+        if !self.practice {
+            if let Some(record) = self
+                .stats
+                .iter()
+                .find(|r| r.game == endpoint && r.date == date)
+            {
+                return if record.won {
+                    Status::Solved
+                } else {
+                    Status::Failed
+                };
+            }
+        }
 
-                    for card in cards {
-                        for word in &words {
-                            if card["content"].as_str().unwrap_or("") == *word {
-                                correct += 1;
-                            }
-                        }
-                    }
+        if !self.progress.contains_key(&self.progress_key(date)) {
+            return Status::NotPlayed;
+        }
 
-                    if correct == 3 && status != 2 {
-                        status = 1;
-                    } else if correct == 4 {
-                        status = 2;
-                    }
-                }
+        // played puzzles are always cached, so one that isn't hasn't been played
+        let game = match self.local(date) {
+            Some(Ok(data)) => self.with_progress(&data, date).ok(),
+            _ => None,
+        };
 
-                self.lines.push(format!(
-                    "{} - {}",
-                    words.join(", "),
-                    match status {
-                        0 => "More than one away",
-                        1 => "One away",
-                        2 => "Correct!",
-                        _ => "Unknown",
-                    }
-                ));
-
-                if status == 2 {
-                    self.used_words
-                        .append(&mut words.iter().map(|x| x.to_string()).collect::<Vec<String>>());
-                }
+        match game {
+            Some(game) => match game.outcome() {
+                Some(outcome) if outcome.won => Status::Solved,
+                Some(_) => Status::Failed,
+                None if game.is_started() => Status::Started,
+                None => Status::NotPlayed,
+            },
+            None => Status::NotPlayed,
+        }
+    }
 
-                if self.used_words.len() == 16 {
-                    self.game_complete = true;
+    /// Closes the calendar, stops waiting for the puzzle being downloaded, and stops any
+    /// prefetch
+    pub fn cancel(&mut self) {
+        if self.calendar.take().is_some() {
+            return;
+        }
 
-                    self.lines.push("Game complete!".to_string());
-                }
+        if self.loading.take().is_some() {
+            self.error = Some("Download cancelled, change the date or game to retry".to_string());
+        }
+
+        if let Some(prefetch) = self.prefetching.take() {
+            self.notice = Some(format!("Cancelled. {}", prefetch.report.summary()));
+        }
+    }
+
+    /// Names practice puzzles in shared results, as they have no number of their own
+    pub fn share_label(&self) -> Option<String> {
+        self.practice
+            .then(|| format!("practice {}", self.date.date_naive()))
+    }
+
+    /// The emoji summary of the current game, if it is complete
+    pub fn share_text(&self) -> Option<String> {
+        self.game.as_ref()?.share(self.share_label().as_deref())
+    }
+
+    /// Queues the current game's result to be written out on exit
+    pub fn share(&mut self) {
+        match self.share_text() {
+            Some(text) if self.shared.contains(&text) => {
+                self.notice = Some("Already shared".to_string());
             }
+            Some(text) => {
+                self.shared.push(text);
 
-            2 => {
-                let guess = self.guesses.last().unwrap().to_lowercase();
+                self.notice = Some(match &self.config.share.file {
+                    Some(path) => format!("Result will be saved to {} on exit", path.display()),
+                    None => "Result will be printed on exit".to_string(),
+                });
+            }
+            None => self.notice = Some("Finish the game to share it".to_string()),
+        }
+    }
 
-                if self.found_words.contains(&guess) {
-                    self.guess_buffer.clear();
+    pub fn toggle_practice(&mut self) {
+        self.practice = !self.practice;
+        self.notice = None;
 
-                    return;
-                }
+        self.load_game();
+    }
 
-                let null_vec = vec![];
+    pub fn left(&mut self) {
+        self.page.prev();
 
-                // detect if its the spangram or not
+        self.load_game();
+    }
 
-                if self.current_game.1["spangram"]
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_lowercase()
-                    == *guess
-                {
-                    self.lines.push(format!("{} is the Spangram!", guess));
+    pub fn right(&mut self) {
+        self.page.next();
 
-                    self.found_words.push(guess.clone());
-                }
+        self.load_game();
+    }
 
-                let mut theme_word = false;
+    pub fn up(&mut self) {
+        self.step(1);
+    }
 
-                for tw in self.current_game.1["themeWords"]
-                    .as_array()
-                    .unwrap_or(&null_vec)
-                {
-                    if tw.as_str().unwrap_or_default().to_lowercase() == *guess {
-                        theme_word = true;
-                    }
-                }
+    pub fn down(&mut self) {
+        self.step(-1);
+    }
 
-                if theme_word {
-                    self.lines.push(format!("{} is a theme word!", guess));
+    /// Moves the date by `days`, staying within the current game's puzzles
+    fn step(&mut self, days: i64) {
+        if let Some(calendar) = &self.calendar {
+            // the calendar moves by a month instead
+            let cursor = calendar.step_months(days as i32);
 
-                    self.found_words.push(guess);
-                }
-                if self.found_words.len() as u8 == self.needed_words {
-                    self.game_complete = true;
+            self.show_calendar(cursor);
 
-                    self.lines.push("Game complete!".to_string());
-                }
-            }
+            return;
+        }
 
-            _ => {
-                self.lines.push(format!(
-                    "Unknown Game Guess {}",
-                    self.guesses.last().unwrap()
-                ));
-            }
+        let mut date = self.date.date_naive() + Duration::days(days);
+
+        if !self.on_stats() {
+            let (first, latest) = self.date_range(self.page.index);
+
+            date = date.clamp(first, latest);
+        }
+
+        if date == self.date.date_naive() {
+            // already at the first or latest puzzle, so there is nothing to load
+            return;
         }
 
-        self.guess_buffer.clear();
+        self.date = local_date(date);
+
+        self.load_game();
+    }
+
+    pub fn quit(&mut self) {
+        self.should_quit = true;
     }
 }
 
@@ -575,74 +802,81 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
     frame.render_widget(tabs, layout[0]);
 
-    // detect if the game is wrodle, connections or strands
+    let title = if app.on_stats() {
+        "Stats".to_string()
+    } else if app.calendar.is_some() {
+        format!(
+            "{}{} archive",
+            app.page.values[app.page.index as usize],
+            if app.practice { " practice" } else { "" }
+        )
+    } else {
+        let metadata = app.game.as_ref().map(|g| g.metadata()).unwrap_or_default();
 
-    let mut text: Vec<Line> = Vec::new();
+        let mut title = app.page.values[app.page.index as usize].clone();
 
-    // the part that describes the game is here
+        if let Some(number) = metadata.number {
+            title += &format!(" #{}", number);
+        }
 
-    for line in &app.game_string {
-        text.push(line.as_str().into());
-    }
+        if app.practice {
+            title += " practice";
+        }
 
-    for line in &app.lines {
-        text.push(line.as_str().into());
-    }
+        title += &format!(" on {}", app.date.date_naive());
 
-    if !app.game_complete {
-        text.push(
-            format!(
-                "GUESS: {}",
-                app.guess_buffer
-                    .iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<_>>()
-                    .join("")
-            )
-            .into(),
-        );
-    }
+        if let Some(constructors) = metadata.constructors {
+            title += &format!(", by {}", constructors);
+        }
 
-    match app.page.index {
-        0 => {
-            frame.render_widget(
-                Paragraph::new(text).block(
-                    Block::bordered()
-                        .title(format!("Wordle on {}", app.date.date_naive()))
-                        .title_alignment(Alignment::Left),
-                ),
-                layout[1],
-            );
+        if let Some(editor) = metadata.editor {
+            title += &format!(", edited by {}", editor);
         }
-        1 => {
-            frame.render_widget(
-                Paragraph::new(text).block(
-                    Block::bordered()
-                        .title(format!("Connections on {}", app.date.date_naive()))
-                        .title_alignment(Alignment::Left),
-                ),
-                layout[1],
-            );
+
+        title
+    };
+
+    let mut block = Block::bordered()
+        .title(title)
+        .title_alignment(Alignment::Left);
+
+    if let Some(notice) = &app.notice {
+        block = block.title_bottom(notice.as_str());
+    }
+
+    let inner = block.inner(layout[1]);
+
+    app.game_area = inner;
+
+    frame.render_widget(block, layout[1]);
+
+    match &app.game {
+        _ if app.on_stats() => stats::draw(frame, inner, &app.stats),
+        _ if app.calendar.is_some() => {
+            if let Some(calendar) = &app.calendar {
+                calendar.draw(frame, inner);
+            }
         }
-        2 => {
+        _ if app.loading.is_some() => {
+            let elapsed = app
+                .loading
+                .map(|(_, start)| start.elapsed())
+                .unwrap_or_default();
+            let spinner = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+
             frame.render_widget(
-                Paragraph::new(text).block(
-                    Block::bordered()
-                        .title(format!("Strands on {}", app.date.date_naive()))
-                        .title_alignment(Alignment::Left),
-                ),
-                layout[1],
+                Paragraph::new(format!("{} Loading… (esc: cancel)", spinner)),
+                inner,
             );
         }
-        _ => {
-            println!("Unknown game!");
-
-            frame.render_widget(Text::from("Unknown Game!"), layout[1]);
-        }
+        Some(game) => game.draw(frame, inner),
+        None => frame.render_widget(Paragraph::new(app.error.clone().unwrap_or_default()), inner),
     }
 
     frame.render_widget(
-        Text::from("Controls: ~: exit, up/down: change date, left/right: change tab"),
+        Text::from(
+            "Controls: ~: exit, tab: game, pgup/pgdn: date, arrows: play or game/date, ctrl-p: practice, ctrl-s: share, ctrl-f: prefetch, ctrl-t: today, ctrl-a: archive, esc: cancel",
+        ),
         layout[2],
     );
 }
@@ -0,0 +1,119 @@
+//! A month calendar of one game's archive, showing how each day's puzzle went.
+
+use chrono::{Datelike, Months, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::collections::BTreeMap;
+
+const WEEKDAYS: &str = "Mo  Tu  We  Th  Fr  Sa  Su";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Status {
+    Solved,
+    Failed,
+    Started,
+    NotPlayed,
+}
+
+impl Status {
+    fn style(self) -> Style {
+        match self {
+            Status::Solved => Style::default().fg(Color::Black).bg(Color::Green),
+            Status::Failed => Style::default().fg(Color::White).bg(Color::Red),
+            Status::Started => Style::default().fg(Color::Black).bg(Color::Yellow),
+            Status::NotPlayed => Style::default(),
+        }
+    }
+}
+
+pub struct Calendar {
+    pub cursor: NaiveDate,                     // the highlighted day
+    pub first: NaiveDate,                      // the game's first puzzle
+    pub latest: NaiveDate,                     // the game's latest puzzle
+    pub statuses: BTreeMap<NaiveDate, Status>, // the days of the cursor's month with a puzzle
+}
+
+/// Every day in the month of `date`
+pub fn month(date: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    let start = date.with_day(1).unwrap_or(date);
+
+    start
+        .iter_days()
+        .take_while(move |d| d.month() == start.month())
+}
+
+impl Calendar {
+    /// The cursor moved by `days`, kept within the archive
+    pub fn step_days(&self, days: i64) -> NaiveDate {
+        (self.cursor + chrono::Duration::days(days)).clamp(self.first, self.latest)
+    }
+
+    /// The cursor moved by `months`, kept within the archive
+    pub fn step_months(&self, months: i32) -> NaiveDate {
+        let moved = if months < 0 {
+            self.cursor
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            self.cursor.checked_add_months(Months::new(months as u32))
+        };
+
+        moved.unwrap_or(self.cursor).clamp(self.first, self.latest)
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let mut text: Vec<Line> = vec![
+            Line::from(self.cursor.format("%B %Y").to_string())
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            "".into(),
+            WEEKDAYS.into(),
+        ];
+
+        // the first week is padded up to the first day of the month
+        let offset = self.cursor.with_day(1).unwrap_or(self.cursor).weekday();
+
+        let mut week = vec![Span::raw(
+            "    ".repeat(offset.num_days_from_monday() as usize),
+        )];
+
+        for day in month(self.cursor) {
+            let mut style = match self.statuses.get(&day) {
+                Some(status) => status.style(),
+                None => Style::default().fg(Color::DarkGray), // outside the archive
+            };
+
+            if day == self.cursor {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+
+            week.push(Span::styled(format!("{:>2}", day.day()), style));
+            week.push(Span::raw("  "));
+
+            if day.weekday().num_days_from_monday() == 6 {
+                text.push(Line::from(std::mem::take(&mut week)));
+            }
+        }
+
+        if !week.is_empty() {
+            text.push(Line::from(week));
+        }
+
+        text.push("".into());
+        text.push(Line::from(vec![
+            Span::styled(" solved ", Status::Solved.style()),
+            Span::raw(" "),
+            Span::styled(" failed ", Status::Failed.style()),
+            Span::raw(" "),
+            Span::styled(" started ", Status::Started.style()),
+            Span::raw(" not played"),
+        ]));
+        text.push("".into());
+        text.push("Arrows: day/week, pgup/pgdn: month, enter: play, esc: close".into());
+
+        frame.render_widget(Paragraph::new(text), area);
+    }
+}
@@ -0,0 +1,133 @@
+//! Command line arguments. Without a subcommand the app is started, otherwise the subcommand
+//! prints its result to stdout, so the tool can be used from scripts.
+
+use crate::app::{self, App};
+use crate::games::GAMES;
+use crate::stats;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use std::error::Error;
+
+#[derive(Parser)]
+#[command(version, about = "New York Times games in the terminal")]
+pub struct Args {
+    /// The game to open: wordle, connections or strands
+    #[arg(short, long, global = true, value_parser = parse_game)]
+    pub game: Option<u8>,
+
+    /// The date of the puzzle to open, as YYYY-MM-DD
+    #[arg(short, long, global = true)]
+    pub date: Option<NaiveDate>,
+
+    /// Play practice puzzles instead of the NYT's
+    #[arg(short, long, global = true)]
+    pub practice: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Downloads the puzzle and prints its data
+    Fetch,
+
+    /// Prints the stats of finished daily games
+    Stats,
+
+    /// Prints the emoji result of a finished game
+    Share,
+
+    /// Prints the answers to the puzzle
+    #[command(alias = "reveal")]
+    Solve,
+
+    /// Caches every game's puzzles in a range of dates, for playing offline
+    Prefetch {
+        /// The first date to download, as YYYY-MM-DD
+        from: NaiveDate,
+
+        /// The last date to download, today if not given
+        to: Option<NaiveDate>,
+    },
+}
+
+/// Finds a game by its name or endpoint, ignoring case
+fn parse_game(name: &str) -> Result<u8, String> {
+    GAMES
+        .iter()
+        .position(|g| g.name.eq_ignore_ascii_case(name) || g.endpoint.eq_ignore_ascii_case(name))
+        .map(|i| i as u8)
+        .ok_or_else(|| {
+            let names: Vec<&str> = GAMES.iter().map(|g| g.endpoint).collect();
+
+            format!("unknown game, expected one of {}", names.join(", "))
+        })
+}
+
+impl Args {
+    /// Points the app at the game and date given on the command line
+    pub fn apply(&self, app: &mut App) {
+        if let Some(game) = self.game {
+            app.page.index = game;
+        }
+
+        if let Some(date) = self.date {
+            app.date = app::local_date(date);
+        }
+
+        if self.practice {
+            app.practice = true;
+        }
+    }
+}
+
+/// The app, if it is on a game rather than the stats page
+fn on_game(app: &mut App) -> Result<&mut App, Box<dyn Error>> {
+    if app.on_stats() {
+        return Err("choose a game with --game".into());
+    }
+
+    Ok(app)
+}
+
+/// Runs a subcommand, returning what it prints
+pub fn run(command: &Command, app: &mut App) -> Result<String, Box<dyn Error>> {
+    let text = match command {
+        Command::Fetch => on_game(app)?.fetch()?,
+        Command::Stats => stats::report(&app.stats),
+        Command::Share => {
+            let app = on_game(app)?;
+
+            app.open()?
+                .share(app.share_label().as_deref())
+                .ok_or("the game hasn't been finished")?
+        }
+        Command::Solve => on_game(app)?.open()?.solution(),
+        Command::Prefetch { from, to } => {
+            let to = to.unwrap_or_else(|| app.config.today.today());
+
+            if *from > to {
+                return Err("the first date is after the last".into());
+            }
+
+            let (requests, mut report) = app.plan_prefetch(*from, to);
+            let (timeout, delay) = (app.config.api.timeout(), app.config.prefetch.delay());
+
+            crate::prefetch::run(requests, timeout, delay, |response| {
+                app.prefetched(response, &mut report);
+
+                // progress goes to stderr, so stdout is only the report
+                eprint!("\r{}", report.summary());
+
+                true
+            });
+
+            eprintln!();
+
+            report.details()
+        }
+    };
+
+    Ok(text)
+}
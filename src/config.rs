@@ -0,0 +1,125 @@
+//! User configuration, read from `config.json` next to the saved state.
+//! Every field is optional, so a missing file is the same as an empty one.
+
+use chrono::{Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Config {
+    pub word_lists: WordListConfig,
+    pub practice: PracticeConfig,
+    pub share: ShareConfig,
+    pub api: ApiConfig,
+    pub prefetch: PrefetchConfig,
+    pub today: TodayConfig,
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct WordListConfig {
+    pub guesses: Vec<PathBuf>, // extra lists of accepted guesses, one word per line
+    pub replace_guesses: bool, // use only `guesses`, instead of adding to the bundled list
+    pub answers: Option<PathBuf>, // the words practice puzzles are picked from
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct PracticeConfig {
+    pub seed: Option<u64>,    // replaces the random seed kept in the state
+    pub dir: Option<PathBuf>, // where practice puzzles are read from
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct ShareConfig {
+    pub file: Option<PathBuf>, // shared results are appended here on exit, instead of printed
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub base_url: Option<String>, // replaces `DEFAULT_BASE_URL`, e.g. with a caching proxy
+    pub timeout: Option<u64>,     // seconds before a download is given up on
+}
+
+pub const DEFAULT_BASE_URL: &str = "https://www.nytimes.com/svc";
+pub const DEFAULT_TIMEOUT: u64 = 10;
+
+/// Overrides both the config and the default base URL
+pub const BASE_URL_VAR: &str = "NYTG_BASE_URL";
+
+impl ApiConfig {
+    /// Where puzzles are downloaded from, without a trailing slash
+    pub fn base_url(&self) -> String {
+        let url = std::env::var(BASE_URL_VAR)
+            .ok()
+            .or_else(|| self.base_url.clone())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        url.trim_end_matches('/').to_string()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout.unwrap_or(DEFAULT_TIMEOUT))
+    }
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct PrefetchConfig {
+    pub days: Option<u64>, // how many days up to the selected date the app prefetches
+    pub delay_ms: Option<u64>, // the wait between downloads
+}
+
+pub const DEFAULT_PREFETCH_DAYS: u64 = 30;
+pub const DEFAULT_PREFETCH_DELAY: u64 = 500;
+
+impl PrefetchConfig {
+    pub fn days(&self) -> u64 {
+        self.days.unwrap_or(DEFAULT_PREFETCH_DAYS).max(1)
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms.unwrap_or(DEFAULT_PREFETCH_DELAY))
+    }
+}
+
+#[derive(serde::Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct TodayConfig {
+    pub follow: Option<bool>, // open today's puzzle on startup if the saved one is finished
+    pub timezone: Option<Tz>, // decides which puzzle is today's, instead of the local time
+}
+
+impl TodayConfig {
+    pub fn follow(&self) -> bool {
+        self.follow.unwrap_or(true)
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        match self.timezone {
+            Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+            None => Local::now().date_naive(),
+        }
+    }
+}
+
+pub fn get_loc() -> PathBuf {
+    crate::state::config_dir().join("config.json")
+}
+
+pub fn load() -> Result<Config, Box<dyn Error>> {
+    let loc = get_loc();
+
+    if !loc.exists() {
+        return Ok(Config::default());
+    }
+
+    let data = std::fs::read_to_string(&loc)?;
+
+    serde_json::from_str(&data).map_err(|e| format!("{}: {}", loc.display(), e).into())
+}
@@ -0,0 +1,113 @@
+//! Downloads puzzles from the API. The app downloads on a background thread, so the UI keeps
+//! drawing while a puzzle loads; the subcommands download directly.
+
+use crate::puzzle::PuzzleError;
+use chrono::{DateTime, Local};
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+pub fn client(timeout: Duration) -> Client {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .unwrap_or_default()
+}
+
+/// Downloads the raw data at `url`. A 404 means the API has no puzzle there.
+pub fn get(client: &Client, url: &str) -> Result<String, PuzzleError> {
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| PuzzleError::Network(e.to_string()))?;
+
+    match response.status() {
+        StatusCode::NOT_FOUND => return Err(PuzzleError::NotFound),
+        status if !status.is_success() => {
+            return Err(PuzzleError::Network(format!(
+                "the server answered {}",
+                status
+            )))
+        }
+        _ => (),
+    }
+
+    response
+        .text()
+        .map_err(|e| PuzzleError::Network(e.to_string()))
+}
+
+pub struct Request {
+    pub id: u64,
+    pub game: u8, // index into `GAMES`
+    pub date: DateTime<Local>,
+    pub url: String,
+}
+
+pub struct Response {
+    pub request: Request,
+    pub result: Result<String, PuzzleError>,
+}
+
+/// A background thread that downloads one puzzle at a time
+pub struct Worker {
+    requests: Sender<Request>,
+    responses: Receiver<Response>,
+    next_id: u64,
+}
+
+impl Worker {
+    pub fn start(timeout: Duration) -> Self {
+        let (requests, request_rx) = mpsc::channel::<Request>();
+        let (response_tx, responses) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let client = client(timeout);
+
+            while let Ok(mut request) = request_rx.recv() {
+                // requests queued behind a newer one are stale, e.g. when holding down a key to
+                // change the date, so only the latest is downloaded
+                while let Ok(newer) = request_rx.try_recv() {
+                    request = newer;
+                }
+
+                let result = get(&client, &request.url);
+
+                if response_tx.send(Response { request, result }).is_err() {
+                    break; // the app is gone
+                }
+            }
+        });
+
+        Self {
+            requests,
+            responses,
+            next_id: 0,
+        }
+    }
+
+    /// Queues a download, returning the id its response will have
+    pub fn send(&mut self, game: u8, date: DateTime<Local>, url: String) -> u64 {
+        self.next_id += 1;
+
+        let _ = self.requests.send(Request {
+            id: self.next_id,
+            game,
+            date,
+            url,
+        });
+
+        self.next_id
+    }
+
+    /// The downloads finished since the last call
+    pub fn finished(&self) -> Vec<Response> {
+        finished(&self.responses)
+    }
+}
+
+/// Every response waiting on `responses`, without blocking for more
+pub fn finished(responses: &Receiver<Response>) -> Vec<Response> {
+    responses.try_iter().collect()
+}
@@ -0,0 +1,572 @@
+use super::{reject, Arrow, Game, Outcome};
+use crate::puzzle::{self, ConnectionsPuzzle, Metadata, PuzzleError};
+use rand::seq::SliceRandom;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use std::collections::BTreeSet;
+
+const HEADER_HEIGHT: u16 = 3; // lines of text above the board
+const TILE_HEIGHT: u16 = 3;
+const MAX_MISTAKES: u8 = 4;
+
+/// The colour of each category, from the easiest to the hardest
+const LEVEL_COLORS: [Color; 4] = [Color::Yellow, Color::Green, Color::Blue, Color::Magenta];
+const LEVEL_EMOJI: [char; 4] = ['🟨', '🟩', '🟦', '🟪'];
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+#[serde(default)]
+struct ConnectionsState {
+    word_order: Vec<String>,        // the cards as laid out, solved rows first
+    selected: Vec<String>,          // the cards in the current guess
+    cursor: usize,                  // index of the highlighted tile among the unsolved cards
+    solved: Vec<usize>,             // categories in the order they were solved or revealed
+    guesses: Vec<BTreeSet<String>>, // every guess submitted, ignoring the order cards were picked
+    mistakes: u8,
+    lines: Vec<String>, // the result of each guess
+    complete: bool,
+}
+
+pub struct Connections {
+    puzzle: ConnectionsPuzzle,
+    state: ConnectionsState,
+}
+
+impl Connections {
+    pub fn new(data: &str) -> Result<Self, PuzzleError> {
+        let puzzle: ConnectionsPuzzle = puzzle::parse(data)?;
+
+        let mut words: Vec<String> = puzzle
+            .categories
+            .iter()
+            .flat_map(|cat| cat.cards.iter().map(|card| card.content.clone()))
+            .collect();
+
+        words.shuffle(&mut rand::thread_rng());
+
+        Ok(Self {
+            puzzle,
+            state: ConnectionsState {
+                word_order: words,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// The cards that are still on the board, in the order they are drawn
+    fn unsolved(&self) -> &[String] {
+        let start = (self.state.solved.len() * 4).min(self.state.word_order.len());
+
+        &self.state.word_order[start..]
+    }
+
+    /// Marks a category as solved, and reflows the board like the web game: the category's cards
+    /// swap places with the cards on the top unsolved row, which then collapses.
+    fn solve(&mut self, cat: usize) {
+        let top = self.state.solved.len() * 4;
+        let cards = &self.puzzle.categories[cat].cards;
+
+        let is_card = |word: &String| cards.iter().any(|card| card.content == *word);
+
+        let order = &mut self.state.word_order;
+
+        let displaced: Vec<usize> = (top..top + 4).filter(|&i| !is_card(&order[i])).collect();
+        let vacated: Vec<usize> = (top + 4..order.len())
+            .filter(|&i| is_card(&order[i]))
+            .collect();
+
+        for (a, b) in displaced.into_iter().zip(vacated) {
+            order.swap(a, b);
+        }
+
+        self.state.solved.push(cat);
+        self.state.selected.retain(|w| !is_card(w));
+        self.state.cursor = 0;
+    }
+
+    /// Shuffles the cards that haven't been solved yet
+    fn shuffle(&mut self) {
+        let top = self.state.solved.len() * 4;
+
+        self.state.word_order[top..].shuffle(&mut rand::thread_rng());
+    }
+
+    fn toggle(&mut self, word: String) {
+        if let Some(i) = self.state.selected.iter().position(|w| *w == word) {
+            self.state.selected.remove(i);
+        } else if self.state.selected.len() < 4 {
+            self.state.selected.push(word);
+        }
+    }
+
+    /// The area of the board, below the header
+    fn board_area(area: Rect) -> Rect {
+        let [_, board] =
+            Layout::vertical([Constraint::Length(HEADER_HEIGHT), Constraint::Min(0)]).areas(area);
+
+        board
+    }
+
+    /// The area of one board row, solved rows first
+    fn row_area(board: Rect, row: usize) -> Rect {
+        Rect {
+            y: board.y + row as u16 * TILE_HEIGHT,
+            height: TILE_HEIGHT,
+            ..board
+        }
+        .intersection(board)
+    }
+
+    fn tile_areas(row: Rect) -> [Rect; 4] {
+        Layout::horizontal([Constraint::Ratio(1, 4); 4]).areas(row)
+    }
+}
+
+impl Game for Connections {
+    fn key(&mut self, c: char) {
+        match c.to_ascii_lowercase() {
+            ' ' => {
+                if let Some(word) = self.unsolved().get(self.state.cursor) {
+                    self.toggle(word.to_string());
+                }
+            }
+            's' => self.shuffle(),
+            'd' => self.state.selected.clear(),
+            _ => reject(
+                &mut self.state.lines,
+                "Select tiles with the arrows and space, s shuffles, d deselects",
+            ),
+        }
+    }
+
+    fn backspace(&mut self) {
+        self.state.selected.pop();
+    }
+
+    fn enter(&mut self) {
+        if self.state.complete {
+            return;
+        }
+
+        let guess: BTreeSet<String> = self.state.selected.iter().cloned().collect();
+
+        // a set, so a card picked twice can't make up a guess of four
+        if guess.len() != 4 {
+            return;
+        }
+
+        if self.state.guesses.contains(&guess) {
+            self.state.lines.push("Already guessed".to_string());
+
+            return;
+        }
+
+        self.state.guesses.push(guess);
+
+        let mut status = 0; // 0 => Incorrect, 1 => One Away, 2 => Correct
+        let mut solved = None;
+
+        for (i, cat) in self.puzzle.categories.iter().enumerate() {
+            let correct = cat
+                .cards
+                .iter()
+                .filter(|card| self.state.selected.contains(&card.content))
+                .count();
+
+            if correct == 3 && status != 2 {
+                status = 1;
+            } else if correct == 4 {
+                status = 2;
+                solved = Some(i);
+            }
+        }
+
+        self.state.lines.push(format!(
+            "{} - {}",
+            self.state.selected.join(", "),
+            match status {
+                0 => "More than one away",
+                1 => "One away",
+                2 => "Correct!",
+                _ => "Unknown",
+            }
+        ));
+
+        if let Some(cat) = solved {
+            self.solve(cat);
+        } else {
+            self.state.mistakes += 1;
+        }
+
+        if self.state.mistakes == MAX_MISTAKES {
+            // game over, so reveal the groups that are left
+
+            for cat in 0..self.puzzle.categories.len() {
+                if !self.state.solved.contains(&cat) {
+                    self.solve(cat);
+                }
+            }
+
+            self.state.selected.clear();
+
+            self.state
+                .lines
+                .push("Out of mistakes! Game over.".to_string());
+
+            self.state.complete = true;
+        } else if self.state.solved.len() == self.puzzle.categories.len() {
+            self.state.lines.push("Game complete!".to_string());
+
+            self.state.complete = true;
+        }
+    }
+
+    fn arrow(&mut self, arrow: Arrow) -> bool {
+        let remaining = self.unsolved().len();
+        let cursor = &mut self.state.cursor;
+
+        match arrow {
+            Arrow::Up => *cursor = cursor.saturating_sub(4),
+            Arrow::Down if *cursor + 4 < remaining => *cursor += 4,
+            Arrow::Left => *cursor = cursor.saturating_sub(1),
+            Arrow::Right if *cursor + 1 < remaining => *cursor += 1,
+            _ => (),
+        }
+
+        true
+    }
+
+    fn click(&mut self, area: Rect, column: u16, row: u16) {
+        let board = Self::board_area(area);
+        let solved = self.state.solved.len();
+
+        let unsolved = self.unsolved().to_vec();
+
+        for (i, word) in unsolved.into_iter().enumerate() {
+            let row_area = Self::row_area(board, solved + i / 4);
+            let tile = Self::tile_areas(row_area)[i % 4];
+
+            if tile.contains((column, row).into()) {
+                self.state.cursor = i;
+
+                self.toggle(word);
+
+                return;
+            }
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let header = Paragraph::new(vec![
+            Line::from("Connections: Group words by a common thread."),
+            Line::from("Arrows move, space selects, enter submits, s: shuffle, d: deselect all."),
+            Line::from(format!(
+                "Mistakes remaining: {}",
+                "* ".repeat((MAX_MISTAKES - self.state.mistakes) as usize)
+            )),
+        ]);
+
+        frame.render_widget(header, area);
+
+        let board = Self::board_area(area);
+
+        // solved categories collapse into a coloured row each
+
+        for (row, &cat) in self.state.solved.iter().enumerate() {
+            let category = &self.puzzle.categories[cat];
+
+            let words = category
+                .cards
+                .iter()
+                .map(|card| card.content.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let style = Style::default().fg(Color::Black).bg(LEVEL_COLORS[cat % 4]);
+
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(category.title.clone())
+                        .style(Style::default().add_modifier(Modifier::BOLD)),
+                    Line::from(words),
+                ])
+                .alignment(Alignment::Center)
+                .style(style),
+                Self::row_area(board, row),
+            );
+        }
+
+        for (i, word) in self.unsolved().iter().enumerate() {
+            let row_area = Self::row_area(board, self.state.solved.len() + i / 4);
+            let tile = Self::tile_areas(row_area)[i % 4];
+
+            let mut style = Style::default();
+
+            if self.state.selected.contains(word) {
+                style = style.fg(Color::White).bg(Color::DarkGray);
+            }
+
+            let mut border = Style::default();
+
+            if i == self.state.cursor {
+                border = border.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+            }
+
+            frame.render_widget(
+                Paragraph::new(word.as_str())
+                    .alignment(Alignment::Center)
+                    .style(style)
+                    .block(Block::bordered().border_style(border)),
+                tile,
+            );
+        }
+
+        // messages go below the board
+
+        let [_, messages] =
+            Layout::vertical([Constraint::Length(TILE_HEIGHT * 4 + 1), Constraint::Min(0)])
+                .areas(board);
+
+        // only the latest messages fit
+        let skip = self
+            .state
+            .lines
+            .len()
+            .saturating_sub(messages.height as usize);
+
+        let lines: Vec<Line> = self
+            .state
+            .lines
+            .iter()
+            .skip(skip)
+            .map(|line| line.as_str().into())
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), messages);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.state.complete
+    }
+
+    fn is_started(&self) -> bool {
+        !self.state.guesses.is_empty()
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        self.state.complete.then_some(Outcome {
+            won: self.state.mistakes < MAX_MISTAKES,
+            guesses: self.state.guesses.len(),
+            mistakes: self.state.mistakes as usize,
+            hints: 0,
+        })
+    }
+
+    fn metadata(&self) -> Metadata {
+        self.puzzle.metadata()
+    }
+
+    fn share(&self, label: Option<&str>) -> Option<String> {
+        if !self.state.complete {
+            return None;
+        }
+
+        let name = self
+            .puzzle
+            .metadata()
+            .name(label, |n| format!("Puzzle #{}", n));
+
+        let mut text = format!("Connections\n{}", name);
+
+        // one row per guess, each card coloured by the category it belongs to
+        for guess in &self.state.guesses {
+            let row: String = guess
+                .iter()
+                .filter_map(|word| {
+                    self.puzzle
+                        .categories
+                        .iter()
+                        .position(|cat| cat.cards.iter().any(|card| card.content == *word))
+                })
+                .map(|cat| LEVEL_EMOJI[cat % 4])
+                .collect();
+
+            text.push('\n');
+            text.push_str(&row);
+        }
+
+        Some(text)
+    }
+
+    fn solution(&self) -> String {
+        self.puzzle
+            .categories
+            .iter()
+            .map(|cat| {
+                let cards: Vec<&str> = cat.cards.iter().map(|c| c.content.as_str()).collect();
+
+                format!("{}: {}", cat.title, cards.join(", "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn save(&self) -> serde_json::Value {
+        serde_json::to_value(&self.state).unwrap_or_default()
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        self.state = serde_json::from_value(state)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const PUZZLE: &str = include_str!("../../tests/fixtures/connections/v2/2024-09-30.json");
+
+    /// Submits a guess of `cards`, in the order given
+    fn guess(connections: &mut Connections, cards: &[&str]) {
+        connections.state.selected = cards.iter().map(|c| c.to_string()).collect();
+        connections.enter();
+    }
+
+    #[test]
+    fn a_guess_with_a_repeated_card_is_ignored() {
+        let mut connections = Connections::new(PUZZLE).unwrap();
+
+        guess(&mut connections, &["BASS", "COD", "BASS", "EEL"]);
+
+        assert!(connections.state.guesses.is_empty());
+        assert_eq!(connections.state.mistakes, 0);
+        assert!(connections.state.solved.is_empty());
+    }
+
+    #[test]
+    fn a_repeated_wrong_guess_is_not_a_mistake() {
+        let mut connections = Connections::new(PUZZLE).unwrap();
+
+        guess(&mut connections, &["BASS", "COD", "EEL", "ASH"]);
+
+        assert_eq!(connections.state.mistakes, 1);
+
+        guess(&mut connections, &["ASH", "EEL", "BASS", "COD"]);
+
+        assert_eq!(connections.state.mistakes, 1);
+        assert_eq!(connections.state.guesses.len(), 1);
+        assert_eq!(
+            connections.state.lines.last().map(String::as_str),
+            Some("Already guessed")
+        );
+    }
+
+    #[test]
+    fn a_solved_category_moves_to_the_top_row() {
+        let mut connections = Connections::new(PUZZLE).unwrap();
+
+        // the fish are spread over every row
+        let layout = [
+            "BASS", "ASH", "ELM", "OAK", "FIR", "COD", "RED", "TAN", "BLUE", "PINK", "EEL", "ONE",
+            "TWO", "SIX", "TEN", "PIKE",
+        ];
+
+        connections.state.word_order = layout.iter().map(|c| c.to_string()).collect();
+
+        let fish = ["BASS", "COD", "EEL", "PIKE"];
+
+        guess(&mut connections, &["PIKE", "EEL", "COD", "BASS"]);
+
+        let sorted = |cards: &[String]| {
+            let mut cards = cards.to_vec();
+
+            cards.sort();
+
+            cards
+        };
+
+        let order = connections.state.word_order.clone();
+
+        assert_eq!(connections.state.solved, [0]);
+        assert_eq!(sorted(&order[..4]), fish);
+
+        // the other 12 cards are all still on the board
+        let rest: Vec<String> = sorted(&layout.map(String::from))
+            .into_iter()
+            .filter(|c| !fish.contains(&c.as_str()))
+            .collect();
+
+        assert_eq!(sorted(&order[4..]), rest);
+
+        // shuffling leaves the solved row alone
+        for _ in 0..10 {
+            connections.shuffle();
+
+            assert_eq!(connections.state.word_order[..4], order[..4]);
+            assert_eq!(sorted(&connections.state.word_order[4..]), rest);
+        }
+    }
+
+    #[test]
+    fn four_mistakes_end_the_game() {
+        let mut connections = Connections::new(PUZZLE).unwrap();
+
+        guess(&mut connections, &["BASS", "COD", "EEL", "ASH"]);
+        guess(&mut connections, &["BASS", "COD", "EEL", "ELM"]);
+        guess(&mut connections, &["BASS", "COD", "EEL", "OAK"]);
+
+        assert!(!connections.state.complete);
+
+        guess(&mut connections, &["BASS", "COD", "EEL", "FIR"]);
+
+        assert!(connections.state.complete);
+        assert_eq!(connections.state.mistakes, MAX_MISTAKES);
+
+        let mut solved = connections.state.solved.clone();
+
+        solved.sort();
+
+        assert_eq!(solved, [0, 1, 2, 3]);
+        assert_eq!(connections.outcome().map(|o| o.won), Some(false));
+    }
+
+    /// The saved state, apart from the messages
+    fn without_lines(connections: &Connections) -> serde_json::Value {
+        let mut state = connections.save();
+
+        state["lines"].take();
+
+        state
+    }
+
+    proptest! {
+        #[test]
+        fn other_keys_only_explain_themselves(
+            keys in prop::collection::vec(
+                any::<char>().prop_filter("used by connections", |c| !" sSdD".contains(*c)),
+                1..20,
+            ),
+        ) {
+            let mut connections = Connections::new(PUZZLE).unwrap();
+
+            // a tile is selected, so deselecting it would show up
+            connections.key(' ');
+
+            let before = without_lines(&connections);
+
+            for c in keys {
+                connections.key(c);
+            }
+
+            prop_assert_eq!(without_lines(&connections), before);
+            prop_assert_eq!(connections.state.lines.len(), 1);
+        }
+    }
+}
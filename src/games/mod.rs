@@ -0,0 +1,128 @@
+//! The games themselves. Each game implements [`Game`] and is listed in [`GAMES`], which is all
+//! the rest of the app needs to know about it.
+
+use crate::puzzle::{Metadata, PuzzleError};
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use ratatui::{layout::Rect, Frame};
+
+mod connections;
+mod strands;
+mod wordle;
+
+pub use connections::Connections;
+pub use strands::Strands;
+pub use wordle::Wordle;
+
+/// A loaded puzzle and the player's progress on it
+pub trait Game {
+    /// Handles a typed character
+    fn key(&mut self, c: char);
+
+    /// Removes the last typed character
+    fn backspace(&mut self);
+
+    /// Submits the current guess
+    fn enter(&mut self);
+
+    /// Handles an arrow key. Returns false if the game doesn't use it, so the app can.
+    fn arrow(&mut self, _arrow: Arrow) -> bool {
+        false
+    }
+
+    /// Handles a left click at `column`, `row`, where `area` is where the game was last drawn
+    fn click(&mut self, _area: Rect, _column: u16, _row: u16) {}
+
+    /// Renders the game inside `area`
+    fn draw(&self, frame: &mut Frame, area: Rect);
+
+    fn is_complete(&self) -> bool;
+
+    /// Whether anything has been played, as opposed to the puzzle only being opened
+    fn is_started(&self) -> bool;
+
+    /// How the game went, once it is complete
+    fn outcome(&self) -> Option<Outcome>;
+
+    /// The NYT style emoji summary of a complete game. The puzzle is named by its number, unless
+    /// `label` names it instead, as for practice puzzles.
+    fn share(&self, label: Option<&str>) -> Option<String>;
+
+    /// The puzzle's number, editor and so on
+    fn metadata(&self) -> Metadata;
+
+    /// The answers to the puzzle, as text
+    fn solution(&self) -> String;
+
+    /// Serializes the player's progress, so it can be saved with the app state
+    fn save(&self) -> serde_json::Value;
+
+    /// Restores progress previously produced by [`Game::save`]
+    fn restore(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error>;
+}
+
+/// The result of a completed game, as recorded in the stats
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Outcome {
+    pub won: bool,
+    pub guesses: usize, // guesses submitted
+    pub mistakes: usize,
+    pub hints: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Arrow {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Everything needed to list, download and create a game
+pub struct GameInfo {
+    pub name: &'static str,
+    pub endpoint: &'static str, // the path under the API's base URL
+    pub first_date: NaiveDate,  // the date of the first puzzle, the start of the archive
+    pub load: fn(&str) -> Result<Box<dyn Game>, PuzzleError>,
+    pub generate: Option<Generate>,
+}
+
+/// Makes the raw data of a practice puzzle, for games that don't need puzzle files
+pub type Generate = fn(&mut StdRng) -> Result<String, PuzzleError>;
+
+pub static GAMES: [GameInfo; 3] = [
+    GameInfo {
+        name: "Wordle",
+        endpoint: "wordle",
+        first_date: NaiveDate::from_ymd_opt(2021, 6, 19).unwrap(),
+        load: |data| Ok(Box::new(Wordle::new(data)?)),
+        generate: Some(Wordle::generate),
+    },
+    GameInfo {
+        name: "Connections",
+        endpoint: "connections",
+        first_date: NaiveDate::from_ymd_opt(2023, 6, 12).unwrap(),
+        load: |data| Ok(Box::new(Connections::new(data)?)),
+        generate: None,
+    },
+    GameInfo {
+        name: "Strands",
+        endpoint: "strands",
+        first_date: NaiveDate::from_ymd_opt(2024, 3, 4).unwrap(),
+        load: |data| Ok(Box::new(Strands::new(data)?)),
+        generate: None,
+    },
+];
+
+/// Adds a message explaining why some input was ignored to a game's `lines`, once even if the
+/// input is repeated
+fn reject(lines: &mut Vec<String>, message: &str) {
+    if lines.last().map(String::as_str) != Some(message) {
+        lines.push(message.to_string());
+    }
+}
+
+/// Names of the registered games, in tab order
+pub fn names() -> Vec<String> {
+    GAMES.iter().map(|g| g.name.to_string()).collect()
+}
@@ -0,0 +1,516 @@
+use super::{reject, Arrow, Game, Outcome};
+use crate::puzzle::{self, Metadata, PuzzleError, StrandsPuzzle};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+const HEADER_HEIGHT: u16 = 4; // lines of text above the board
+const CELL_WIDTH: u16 = 4; // a letter padded to three columns, and a gap
+const WORDS_PER_HINT: usize = 3; // non-theme words needed to earn a hint
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+#[serde(default)]
+struct StrandsState {
+    cursor: [usize; 2],       // [row, column] of the cursor
+    path: Vec<[usize; 2]>,    // the cells selected so far
+    found_words: Vec<String>, // theme words and the spangram, as written in the puzzle
+    other_words: Vec<String>, // valid words that are not theme words, towards hints
+    hints_at: Vec<usize>,     // how many words had been found when each hint was used
+    guesses: usize,           // words submitted, long enough to be checked
+    hinted: Option<String>,   // theme word whose cells are shown by the last hint
+    lines: Vec<String>,
+    complete: bool,
+}
+
+pub struct Strands {
+    puzzle: StrandsPuzzle,
+    state: StrandsState,
+}
+
+impl Strands {
+    pub fn new(data: &str) -> Result<Self, PuzzleError> {
+        Ok(Self {
+            puzzle: puzzle::parse(data)?,
+            state: StrandsState::default(),
+        })
+    }
+
+    /// The theme words plus the spangram
+    fn needed_words(&self) -> usize {
+        self.puzzle.theme_words.len() + 1
+    }
+
+    fn rows(&self) -> usize {
+        self.puzzle.starting_board.len()
+    }
+
+    fn columns(&self) -> usize {
+        self.puzzle.starting_board[0].chars().count()
+    }
+
+    /// Adds a cell to the path, following the rules of the web game: the path must be made of
+    /// adjacent cells, going back to a selected cell shortens the path to it, and selecting the
+    /// last cell again submits the word.
+    fn select(&mut self, cell: [usize; 2]) {
+        let path = &mut self.state.path;
+
+        if path.last() == Some(&cell) {
+            self.enter();
+        } else if let Some(i) = path.iter().position(|&c| c == cell) {
+            path.truncate(i + 1);
+        } else if path.last().is_none_or(|&last| adjacent(last, cell)) {
+            path.push(cell);
+        } else {
+            *path = vec![cell];
+        }
+    }
+
+    /// Hints earned but not yet used
+    fn hints_available(&self) -> usize {
+        (self.state.other_words.len() / WORDS_PER_HINT).saturating_sub(self.state.hints_at.len())
+    }
+
+    /// Whether a word can be made on the board. The puzzle lists every such word, so no separate
+    /// dictionary is needed.
+    fn is_word(&self, word: &str) -> bool {
+        self.puzzle
+            .solutions
+            .iter()
+            .any(|w| w.eq_ignore_ascii_case(word))
+    }
+
+    /// Reveals the cells of a theme word that hasn't been found yet, or of the spangram
+    fn hint(&mut self) {
+        if let Some(hinted) = &self.state.hinted {
+            if !self.state.found_words.contains(hinted) {
+                self.state
+                    .lines
+                    .push("Find the hinted word first".to_string());
+
+                return;
+            }
+        }
+
+        if self.hints_available() == 0 {
+            self.state.lines.push(format!(
+                "Find {} more words to earn a hint",
+                WORDS_PER_HINT - self.state.other_words.len() % WORDS_PER_HINT
+            ));
+
+            return;
+        }
+
+        // the spangram is only hinted once every theme word has been found
+        let unfound = self
+            .puzzle
+            .theme_words
+            .iter()
+            .chain([&self.puzzle.spangram])
+            .find(|w| !self.state.found_words.contains(w));
+
+        let Some(word) = unfound else {
+            self.state
+                .lines
+                .push("Every word has been found".to_string());
+
+            return;
+        };
+
+        self.state.lines.push(if *word == self.puzzle.spangram {
+            "Hint: the spangram is outlined".to_string()
+        } else {
+            "Hint: a theme word is outlined".to_string()
+        });
+
+        self.state.hinted = Some(word.clone());
+        self.state.hints_at.push(self.state.found_words.len());
+    }
+
+    /// The board cell drawn at `column`, `row`
+    fn cell_at(&self, area: Rect, column: u16, row: u16) -> Option<[usize; 2]> {
+        let x = column.checked_sub(area.x)?;
+        let y = row.checked_sub(area.y + HEADER_HEIGHT)? as usize;
+
+        if x % CELL_WIDTH == CELL_WIDTH - 1 {
+            return None; // the gap between two letters
+        }
+
+        let x = (x / CELL_WIDTH) as usize;
+
+        (y < self.rows() && x < self.columns()).then_some([y, x])
+    }
+
+    /// The cells of a theme word or the spangram
+    fn coords(&self, word: &str) -> Option<&Vec<[usize; 2]>> {
+        if word == self.puzzle.spangram {
+            Some(&self.puzzle.spangram_coords)
+        } else {
+            self.puzzle.theme_coords.get(word)
+        }
+    }
+
+    /// The style of every cell in a found or hinted word
+    fn found_cells(&self) -> Vec<([usize; 2], Style)> {
+        let mut cells = Vec::new();
+
+        for word in &self.state.found_words {
+            let style = if *word == self.puzzle.spangram {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::LightBlue)
+            };
+
+            if let Some(coords) = self.coords(word) {
+                cells.extend(coords.iter().map(|&c| (c, style)));
+            }
+        }
+
+        // after the found words, so a found hinted word is drawn as found
+
+        if let Some(coords) = self.state.hinted.as_deref().and_then(|w| self.coords(w)) {
+            let style = Style::default()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+            cells.extend(coords.iter().map(|&c| (c, style)));
+        }
+
+        cells
+    }
+}
+
+/// Whether two cells touch, including diagonally
+fn adjacent(a: [usize; 2], b: [usize; 2]) -> bool {
+    a != b && a[0].abs_diff(b[0]) <= 1 && a[1].abs_diff(b[1]) <= 1
+}
+
+impl Game for Strands {
+    fn key(&mut self, c: char) {
+        match c.to_ascii_lowercase() {
+            ' ' => self.select(self.state.cursor),
+            'h' => self.hint(),
+            _ => reject(
+                &mut self.state.lines,
+                "Trace words with the arrows and space, or press h for a hint",
+            ),
+        }
+    }
+
+    fn backspace(&mut self) {
+        self.state.path.pop();
+    }
+
+    fn enter(&mut self) {
+        if self.state.complete || self.state.path.is_empty() {
+            return;
+        }
+
+        let path = std::mem::take(&mut self.state.path);
+        let word = self.puzzle.spell(&path);
+
+        if path.len() < 4 {
+            self.state.lines.push(format!("{} is too short", word));
+
+            return;
+        }
+
+        self.state.guesses += 1;
+
+        // a word only counts when traced along its own cells
+
+        let found = if path == self.puzzle.spangram_coords {
+            Some(self.puzzle.spangram.clone())
+        } else {
+            self.puzzle
+                .theme_coords
+                .iter()
+                .find(|(_, coords)| **coords == path)
+                .map(|(word, _)| word.clone())
+        };
+
+        match found {
+            Some(found) if self.state.found_words.contains(&found) => {
+                self.state
+                    .lines
+                    .push(format!("{} was already found", found));
+            }
+            Some(found) => {
+                if found == self.puzzle.spangram {
+                    self.state.lines.push(format!("{} is the Spangram!", found));
+                } else {
+                    self.state.lines.push(format!("{} is a theme word!", found));
+                }
+
+                self.state.found_words.push(found);
+            }
+            None if !self.is_word(&word) => {
+                self.state
+                    .lines
+                    .push(format!("{} is not in the word list", word));
+            }
+            None if self.state.other_words.contains(&word) => {
+                self.state.lines.push(format!("{} was already found", word));
+            }
+            None => {
+                self.state.other_words.push(word.clone());
+
+                self.state.lines.push(format!(
+                    "{} is not a theme word ({}/{} towards a hint)",
+                    word,
+                    (self.state.other_words.len() - 1) % WORDS_PER_HINT + 1,
+                    WORDS_PER_HINT
+                ));
+            }
+        }
+
+        if self.state.found_words.len() == self.needed_words() {
+            self.state.complete = true;
+            self.state.hinted = None;
+
+            self.state.lines.push(format!(
+                "Game complete! Hints used: {}",
+                self.state.hints_at.len()
+            ));
+        }
+    }
+
+    fn arrow(&mut self, arrow: Arrow) -> bool {
+        let (rows, columns) = (self.rows(), self.columns());
+        let [row, col] = &mut self.state.cursor;
+
+        match arrow {
+            Arrow::Up => *row = row.saturating_sub(1),
+            Arrow::Down => *row = (*row + 1).min(rows - 1),
+            Arrow::Left => *col = col.saturating_sub(1),
+            Arrow::Right => *col = (*col + 1).min(columns - 1),
+        }
+
+        true
+    }
+
+    fn click(&mut self, area: Rect, column: u16, row: u16) {
+        if self.state.complete {
+            return;
+        }
+
+        if let Some(cell) = self.cell_at(area, column, row) {
+            self.state.cursor = cell;
+
+            self.select(cell);
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let mut text: Vec<Line> = vec![
+            "Strands: Uncover words. Arrows move, space selects, enter submits, h: hint.".into(),
+            format!("Clue: {}", self.puzzle.clue).into(),
+            format!(
+                "Found {} of {} theme words. Hints: {} ({}/{} towards the next)",
+                self.state.found_words.len(),
+                self.needed_words(),
+                self.hints_available(),
+                self.state.other_words.len() % WORDS_PER_HINT,
+                WORDS_PER_HINT
+            )
+            .into(),
+            "".into(),
+        ];
+
+        let found = self.found_cells();
+
+        for (r, line) in self.puzzle.starting_board.iter().enumerate() {
+            let mut spans = Vec::new();
+
+            for (c, letter) in line.chars().enumerate() {
+                let cell = [r, c];
+
+                let mut style = found
+                    .iter()
+                    .find(|(f, _)| *f == cell)
+                    .map(|(_, style)| *style)
+                    .unwrap_or_default();
+
+                if self.state.path.contains(&cell) {
+                    style = style.fg(Color::White).bg(Color::DarkGray);
+                }
+
+                if !self.state.complete && self.state.cursor == cell {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+
+                spans.push(Span::styled(format!(" {} ", letter), style));
+                spans.push(Span::raw(" "));
+            }
+
+            text.push(Line::from(spans));
+        }
+
+        text.push("".into());
+
+        if !self.state.complete {
+            text.push(format!("Selected: {}", self.puzzle.spell(&self.state.path)).into());
+        }
+
+        for line in &self.state.lines {
+            text.push(line.as_str().into());
+        }
+
+        frame.render_widget(Paragraph::new(text), area);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.state.complete
+    }
+
+    fn is_started(&self) -> bool {
+        self.state.guesses > 0 || !self.state.hints_at.is_empty()
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        // there is no way to lose Strands
+        self.state.complete.then_some(Outcome {
+            won: true,
+            guesses: self.state.guesses,
+            mistakes: 0,
+            hints: self.state.hints_at.len(),
+        })
+    }
+
+    fn metadata(&self) -> Metadata {
+        self.puzzle.metadata()
+    }
+
+    fn share(&self, label: Option<&str>) -> Option<String> {
+        if !self.state.complete {
+            return None;
+        }
+
+        let mut icons = Vec::new();
+
+        for (i, word) in self.state.found_words.iter().enumerate() {
+            let hints = self.state.hints_at.iter().filter(|&&at| at == i).count();
+
+            icons.extend(std::iter::repeat_n('💡', hints));
+            icons.push(if *word == self.puzzle.spangram {
+                '🟡'
+            } else {
+                '🔵'
+            });
+        }
+
+        let name = self.puzzle.metadata().name(label, |n| format!("#{}", n));
+
+        let mut text = format!("Strands {}\n“{}”", name, self.puzzle.clue);
+
+        for row in icons.chunks(4) {
+            text.push('\n');
+            text.extend(row);
+        }
+
+        Some(text)
+    }
+
+    fn solution(&self) -> String {
+        format!(
+            "Spangram: {}\nTheme words: {}",
+            self.puzzle.spangram,
+            self.puzzle.theme_words.join(", ")
+        )
+    }
+
+    fn save(&self) -> serde_json::Value {
+        serde_json::to_value(&self.state).unwrap_or_default()
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        self.state = serde_json::from_value(state)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const PUZZLE: &str = include_str!("../../tests/fixtures/strands/v2/2024-09-30.json");
+
+    /// The saved state, apart from the messages
+    fn without_lines(strands: &Strands) -> serde_json::Value {
+        let mut state = strands.save();
+
+        state["lines"].take();
+
+        state
+    }
+
+    proptest! {
+        #[test]
+        fn other_keys_only_explain_themselves(
+            keys in prop::collection::vec(
+                any::<char>().prop_filter("used by strands", |c| !" hH".contains(*c)),
+                1..20,
+            ),
+        ) {
+            let mut strands = Strands::new(PUZZLE).unwrap();
+            let before = without_lines(&strands);
+
+            for c in keys {
+                strands.key(c);
+            }
+
+            prop_assert_eq!(without_lines(&strands), before);
+            prop_assert_eq!(strands.state.lines.len(), 1);
+        }
+    }
+
+    /// Traces a path of cells and submits it
+    fn trace(strands: &mut Strands, path: &[[usize; 2]]) {
+        strands.state.path = path.to_vec();
+        strands.enter();
+    }
+
+    #[test]
+    fn the_spangram_is_hinted_last() {
+        let mut strands = Strands::new(PUZZLE).unwrap();
+
+        // three non-theme words earn a hint
+        trace(&mut strands, &[[0, 0], [0, 1], [1, 1], [1, 0]]);
+        trace(&mut strands, &[[0, 1], [0, 0], [1, 0], [1, 1]]);
+        trace(&mut strands, &[[1, 1], [1, 0], [0, 0], [0, 1]]);
+
+        trace(&mut strands, &[[1, 0], [1, 1], [1, 2], [1, 3]]);
+        trace(&mut strands, &[[2, 3], [2, 2], [2, 1], [2, 0]]);
+
+        strands.key('h');
+
+        assert_eq!(strands.state.hinted.as_deref(), Some("ABCD"));
+        assert_eq!(strands.hints_available(), 0);
+        assert_eq!(
+            strands.state.lines.last().map(String::as_str),
+            Some("Hint: the spangram is outlined")
+        );
+        assert!(strands
+            .found_cells()
+            .iter()
+            .any(|(cell, _)| *cell == [0, 3]));
+    }
+
+    #[test]
+    fn a_puzzle_needs_a_word_list() {
+        let mut puzzle: serde_json::Value = serde_json::from_str(PUZZLE).unwrap();
+
+        puzzle["solutions"] = serde_json::json!([]);
+
+        assert!(Strands::new(&puzzle.to_string()).is_err());
+
+        puzzle.as_object_mut().unwrap().remove("solutions");
+
+        assert!(Strands::new(&puzzle.to_string()).is_err());
+    }
+}
@@ -0,0 +1,610 @@
+use super::{Game, Outcome};
+use crate::puzzle::{self, Metadata, PuzzleError, WordlePuzzle};
+use crate::words;
+use rand::{rngs::StdRng, seq::SliceRandom};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::collections::HashMap;
+use GameResult::*;
+
+// ordered from the least to the most information about a letter
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+enum GameResult {
+    Grey,
+    Yellow,
+    Green,
+}
+
+impl GameResult {
+    fn style(self) -> Style {
+        match self {
+            Green => Style::default().fg(Color::Black).bg(Color::Green),
+            Yellow => Style::default().fg(Color::Black).bg(Color::Yellow),
+            Grey => Style::default().fg(Color::White).bg(Color::DarkGray),
+        }
+    }
+
+    fn emoji(self) -> char {
+        match self {
+            Green => '🟩',
+            Yellow => '🟨',
+            Grey => '⬛',
+        }
+    }
+}
+
+const KEYBOARD: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+const MAX_GUESSES: usize = 6;
+const ORDINALS: [&str; 5] = ["1st", "2nd", "3rd", "4th", "5th"];
+
+const TYPED_STYLE: Style = Style::new().fg(Color::Black).bg(Color::Gray);
+const EMPTY_STYLE: Style = Style::new().bg(Color::Gray);
+
+/// A row of letter tiles on the board
+fn tile_row(tiles: impl Iterator<Item = (char, Style)>) -> Line<'static> {
+    let mut spans = Vec::new();
+
+    for (c, style) in tiles {
+        spans.push(Span::styled(format!(" {} ", c.to_uppercase()), style));
+        spans.push(Span::raw(" "));
+    }
+
+    Line::from(spans)
+}
+
+/// Formats a number with commas, like the Wordle number in shared results: 1,234
+fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut text = String::new();
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            text.push(',');
+        }
+
+        text.push(c);
+    }
+
+    text
+}
+
+/// Lowercases a typed letter. Anything that isn't a single letter is rejected.
+fn letter(c: char) -> Option<char> {
+    let mut lower = c.to_lowercase();
+
+    match (lower.next(), lower.next()) {
+        (Some(l), None) if l.is_alphabetic() => Some(l),
+        _ => None,
+    }
+}
+
+/// Scores a guess against the answer. Only the first five letters of each are compared, and a
+/// missing letter never matches, so no input can make this panic.
+fn calc_game(correct: &str, guess: &str) -> [GameResult; 5] {
+    const ARRAY_REPEAT_VALUE: GameResult = GameResult::Grey;
+
+    let mut res = [ARRAY_REPEAT_VALUE; 5];
+
+    let correct_chars: Vec<char> = correct.chars().take(5).collect();
+    let guess_chars: Vec<char> = guess.chars().take(5).collect();
+
+    // keyed by letter, so word lists in other alphabets work too
+    let mut correct_count: HashMap<char, usize> = HashMap::new();
+
+    // First pass: identify all Green matches
+    for (i, c) in correct_chars.iter().enumerate() {
+        if guess_chars.get(i) == Some(c) {
+            res[i] = Green;
+        } else {
+            *correct_count.entry(*c).or_default() += 1;
+        }
+    }
+
+    // Second pass: identify Yellow matches
+    for (i, c) in guess_chars.iter().enumerate() {
+        if res[i] != Green {
+            if let Some(count) = correct_count.get_mut(c).filter(|c| **c > 0) {
+                res[i] = Yellow;
+                *count -= 1;
+            }
+        }
+    }
+
+    res
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Default)]
+#[serde(default)]
+struct WordleState {
+    guess_buffer: Vec<char>, // The guess
+    guesses: Vec<String>,    // Guesses are stored here
+    complete: bool,
+    won: bool,       // false when the game was lost, or isn't complete yet
+    hard_mode: bool, // revealed hints must be used in later guesses
+
+    #[serde(skip)]
+    message: String, // why the last guess was rejected
+}
+
+pub struct Wordle {
+    puzzle: WordlePuzzle,
+    state: WordleState,
+}
+
+impl Wordle {
+    pub fn new(data: &str) -> Result<Self, PuzzleError> {
+        let mut puzzle: WordlePuzzle = puzzle::parse(data)?;
+
+        puzzle.solution = puzzle.solution.to_lowercase();
+
+        Ok(Self {
+            puzzle,
+            state: WordleState::default(),
+        })
+    }
+
+    /// Makes a practice puzzle from the answers list
+    pub fn generate(rng: &mut StdRng) -> Result<String, PuzzleError> {
+        let solution = words::get()
+            .answers
+            .choose(rng)
+            .ok_or_else(|| PuzzleError::NoPractice("The answers list is empty".to_string()))?;
+
+        Ok(serde_json::json!({ "solution": solution }).to_string())
+    }
+
+    /// In hard mode, checks that a guess uses every hint revealed so far: green letters stay in
+    /// place and yellow letters appear somewhere.
+    fn hard_mode_error(&self, guess: &str) -> Option<String> {
+        let guess: Vec<char> = guess.chars().collect();
+
+        for previous in &self.state.guesses {
+            let results = calc_game(&self.puzzle.solution, previous);
+
+            // how many of each letter are known to be in the answer
+            let mut needed: HashMap<char, usize> = HashMap::new();
+
+            for (i, (c, result)) in previous.chars().zip(results).enumerate() {
+                if result == Green && guess.get(i) != Some(&c) {
+                    return Some(format!(
+                        "{} letter must be {}",
+                        ORDINALS[i],
+                        c.to_uppercase()
+                    ));
+                }
+
+                if result != Grey {
+                    *needed.entry(c).or_default() += 1;
+                }
+            }
+
+            // in the order of the previous guess, so the message is predictable
+            for c in previous.chars() {
+                if guess.iter().filter(|&&g| g == c).count() < needed.get(&c).copied().unwrap_or(0)
+                {
+                    return Some(format!("Guess must contain {}", c.to_uppercase()));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The best result each letter has had in any guess so far
+    fn letter_results(&self) -> HashMap<char, GameResult> {
+        let mut letters = HashMap::new();
+
+        for guess in &self.state.guesses {
+            let results = calc_game(&self.puzzle.solution, guess);
+
+            for (c, result) in guess.chars().zip(results) {
+                let letter = letters.entry(c).or_insert(result);
+
+                *letter = (*letter).max(result);
+            }
+        }
+
+        letters
+    }
+
+    /// Draws a QWERTY keyboard, with each letter coloured by what is known about it
+    fn draw_keyboard(&self, frame: &mut Frame, area: Rect) {
+        let letters = self.letter_results();
+
+        let rows: Vec<Line> = KEYBOARD
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let tiles = row.chars().map(|c| {
+                    let style = match letters.get(&c) {
+                        Some(result) => result.style(),
+                        None => TYPED_STYLE,
+                    };
+
+                    (c, style)
+                });
+
+                let mut line = tile_row(tiles);
+
+                // stagger the rows like a real keyboard
+                line.spans.insert(0, Span::raw(" ".repeat(i * 2)));
+
+                line
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(rows), area);
+    }
+}
+
+impl Game for Wordle {
+    fn key(&mut self, c: char) {
+        if c == '!' {
+            // like the web game, hard mode can only be turned on before the first guess
+            if self.state.hard_mode || self.state.guesses.is_empty() {
+                self.state.hard_mode = !self.state.hard_mode;
+            } else {
+                self.state.message = "Hard mode can only be turned on at the start".to_string();
+            }
+
+            return;
+        }
+
+        let Some(c) = letter(c) else {
+            self.state.message = format!("{:?} is not a letter", c);
+
+            return;
+        };
+
+        self.state.message.clear();
+
+        if self.state.guess_buffer.len() != 5 {
+            self.state.guess_buffer.push(c);
+        }
+    }
+
+    fn backspace(&mut self) {
+        self.state.guess_buffer.pop();
+    }
+
+    fn enter(&mut self) {
+        if self.state.complete || self.state.guess_buffer.len() != 5 {
+            return;
+        }
+
+        let guess: String = self.state.guess_buffer.iter().collect();
+
+        // the answer is accepted even if a replacement word list lacks it
+        if guess != self.puzzle.solution && !words::is_guess(&guess) {
+            self.state.message = "Not in word list".to_string();
+
+            return;
+        }
+
+        if self.state.hard_mode {
+            if let Some(error) = self.hard_mode_error(&guess) {
+                self.state.message = error;
+
+                return;
+            }
+        }
+
+        self.state.message.clear();
+
+        if guess == self.puzzle.solution {
+            // correct guess :D
+
+            self.state.complete = true;
+            self.state.won = true;
+        }
+
+        self.state.guesses.push(guess);
+
+        if self.state.guesses.len() == MAX_GUESSES {
+            // out of attempts, so the game is over either way
+            self.state.complete = true;
+        }
+
+        self.state.guess_buffer.clear();
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let mut text: Vec<Line> = vec![
+            "Wordle: Guess a five letter word to win the game.".into(),
+            format!(
+                "Hard mode: {} (!: toggle)",
+                if self.state.hard_mode { "on" } else { "off" }
+            )
+            .into(),
+            "".into(),
+        ];
+
+        // color the grid, one row of tiles per attempt with a gap between rows
+
+        for guess in &self.state.guesses {
+            let results = calc_game(&self.puzzle.solution, guess);
+
+            let tiles = guess
+                .chars()
+                .zip(results)
+                .map(|(c, result)| (c, result.style()));
+
+            text.push(tile_row(tiles));
+            text.push("".into());
+        }
+
+        if !self.state.complete {
+            // the row being typed, padded with blanks
+
+            let typed = self.state.guess_buffer.iter().map(|&c| (c, TYPED_STYLE));
+            let blanks = std::iter::repeat((' ', EMPTY_STYLE));
+
+            text.push(tile_row(typed.chain(blanks).take(5)));
+            text.push("".into());
+        }
+
+        // the remaining attempts
+
+        let rows = self.state.guesses.len() + usize::from(!self.state.complete);
+
+        for _ in rows..MAX_GUESSES {
+            text.push(tile_row(std::iter::repeat_n((' ', EMPTY_STYLE), 5)));
+            text.push("".into());
+        }
+
+        if self.state.won {
+            text.push(format!("You won in {}/{}!", self.state.guesses.len(), MAX_GUESSES).into());
+        } else if self.state.complete {
+            text.push(
+                format!(
+                    "Out of guesses! The word was {}.",
+                    self.puzzle.solution.to_uppercase()
+                )
+                .into(),
+            );
+        } else {
+            text.push(self.state.message.as_str().into());
+        }
+
+        // the keyboard goes under the board
+
+        let [board, keyboard] = Layout::vertical([
+            Constraint::Length(text.len() as u16 + 1),
+            Constraint::Length(KEYBOARD.len() as u16),
+        ])
+        .areas(area);
+
+        frame.render_widget(Paragraph::new(text), board);
+
+        self.draw_keyboard(frame, keyboard);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.state.complete
+    }
+
+    fn is_started(&self) -> bool {
+        !self.state.guesses.is_empty()
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        self.state.complete.then_some(Outcome {
+            won: self.state.won,
+            guesses: self.state.guesses.len(),
+            mistakes: 0,
+            hints: 0,
+        })
+    }
+
+    fn metadata(&self) -> Metadata {
+        self.puzzle.metadata()
+    }
+
+    fn share(&self, label: Option<&str>) -> Option<String> {
+        if !self.state.complete {
+            return None;
+        }
+
+        let score = if self.state.won {
+            self.state.guesses.len().to_string()
+        } else {
+            "X".to_string()
+        };
+
+        let name = self.puzzle.metadata().name(label, thousands);
+
+        let mut text = format!(
+            "Wordle {} {}/{}{}\n",
+            name,
+            score,
+            MAX_GUESSES,
+            if self.state.hard_mode { "*" } else { "" }
+        );
+
+        for guess in &self.state.guesses {
+            let row: String = calc_game(&self.puzzle.solution, guess)
+                .into_iter()
+                .map(GameResult::emoji)
+                .collect();
+
+            text.push('\n');
+            text.push_str(&row);
+        }
+
+        Some(text)
+    }
+
+    fn solution(&self) -> String {
+        self.puzzle.solution.to_uppercase()
+    }
+
+    fn save(&self) -> serde_json::Value {
+        serde_json::to_value(&self.state).unwrap_or_default()
+    }
+
+    fn restore(&mut self, state: serde_json::Value) -> Result<(), serde_json::Error> {
+        self.state = serde_json::from_value(state)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A key press, as the app passes them to a game
+    #[derive(Clone, Debug)]
+    enum Input {
+        Key(char),
+        Word(&'static str), // typed a letter at a time
+        Backspace,
+        Enter,
+    }
+
+    fn input() -> impl Strategy<Value = Input> {
+        prop_oneof![
+            4 => any::<char>().prop_map(Input::Key),
+            2 => "[a-z]".prop_map(|s| Input::Key(s.chars().next().unwrap())),
+            2 => prop::sample::select(vec!["crane", "slate", "adieu", "pious"]).prop_map(Input::Word),
+            1 => Just(Input::Backspace),
+            1 => Just(Input::Enter),
+        ]
+    }
+
+    /// A game of `solution` with `guesses` already made
+    fn played(solution: &str, guesses: &[&str]) -> Wordle {
+        let mut wordle =
+            Wordle::new(&serde_json::json!({ "solution": solution }).to_string()).unwrap();
+
+        wordle.state.guesses = guesses.iter().map(|g| g.to_string()).collect();
+
+        wordle
+    }
+
+    #[test]
+    fn hard_mode_keeps_green_letters_in_place() {
+        let wordle = played("crane", &["slate"]);
+
+        assert_eq!(
+            wordle.hard_mode_error("crank"),
+            Some("5th letter must be E".to_string())
+        );
+        assert_eq!(
+            wordle.hard_mode_error("bread"),
+            Some("3rd letter must be A".to_string())
+        );
+        assert_eq!(wordle.hard_mode_error("grace"), None);
+    }
+
+    #[test]
+    fn hard_mode_needs_yellow_letters_somewhere() {
+        let wordle = played("crane", &["react"]);
+
+        assert_eq!(wordle.hard_mode_error("brace"), None);
+        assert_eq!(
+            wordle.hard_mode_error("plank"),
+            Some("Guess must contain R".to_string())
+        );
+
+        // letters outside ASCII are named as their tiles show them
+        let wordle = played("étude", &["xxxxé"]);
+
+        assert_eq!(
+            wordle.hard_mode_error("zzzzz"),
+            Some("Guess must contain É".to_string())
+        );
+    }
+
+    #[test]
+    fn hard_mode_counts_repeated_letters() {
+        // B and E are green, the other B and the A are yellow
+        let wordle = played("abbey", &["babes"]);
+
+        assert_eq!(
+            wordle.hard_mode_error("ebbed"),
+            Some("Guess must contain A".to_string())
+        );
+        assert_eq!(wordle.hard_mode_error("abbes"), None);
+
+        // only one B was revealed, so one is enough
+        let wordle = played("abbey", &["bxxxx"]);
+
+        assert_eq!(wordle.hard_mode_error("zzzzb"), None);
+
+        // both Bs were revealed, so both are needed
+        let wordle = played("abbey", &["bxxbx"]);
+
+        assert_eq!(
+            wordle.hard_mode_error("bzzzz"),
+            Some("Guess must contain B".to_string())
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn calc_game_accepts_any_input(correct in ".*", guess in ".*") {
+            calc_game(&correct, &guess);
+        }
+
+        #[test]
+        fn calc_game_never_colours_more_letters_than_the_answer_has(
+            correct in "[a-e]{5}",
+            guess in "[a-e]{5}",
+        ) {
+            let results = calc_game(&correct, &guess);
+
+            for c in guess.chars() {
+                let coloured = guess
+                    .chars()
+                    .zip(results)
+                    .filter(|&(g, r)| g == c && r != Grey)
+                    .count();
+
+                prop_assert!(coloured <= correct.chars().filter(|&a| a == c).count());
+            }
+        }
+
+        #[test]
+        fn the_answer_is_all_green(word in "\\p{L}{5}") {
+            let word = word.to_lowercase();
+
+            prop_assert_eq!(calc_game(&word, &word), [Green; 5]);
+        }
+
+        #[test]
+        fn any_input_keeps_the_game_valid(
+            solution in "\\p{L}{5}",
+            inputs in prop::collection::vec(input(), 0..80),
+        ) {
+            let mut wordle = Wordle::new(&serde_json::json!({ "solution": solution }).to_string())
+                .unwrap();
+
+            for input in inputs {
+                match input {
+                    Input::Key(c) => wordle.key(c),
+                    Input::Word(word) => word.chars().for_each(|c| wordle.key(c)),
+                    Input::Backspace => wordle.backspace(),
+                    Input::Enter => wordle.enter(),
+                }
+
+                let state = &wordle.state;
+
+                prop_assert!(state.guess_buffer.len() <= 5);
+                prop_assert!(state.guess_buffer.iter().all(|&c| letter(c) == Some(c)));
+                prop_assert!(state.guesses.len() <= MAX_GUESSES);
+                prop_assert!(state.guesses.iter().all(|g| g.chars().count() == 5));
+            }
+
+            // drawing and sharing go through calc_game for every guess
+            wordle.letter_results();
+            wordle.share(None);
+        }
+    }
+}
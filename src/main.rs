@@ -2,12 +2,19 @@
 //! Supports Wordle, Connections and Strands so far
 
 use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
 
 use app::App;
+use clap::Parser;
+use games::Arrow;
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
-        event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind,
+            KeyModifiers, MouseButton, MouseEventKind,
+        },
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
@@ -15,10 +22,28 @@ use ratatui::{
 };
 
 mod app; // The application UI
+mod calendar; // Month calendar of a game's archive
+mod cli; // Command line arguments and subcommands
+mod config; // User configuration
+mod download; // Downloads puzzles, in the background for the app
+mod games; // The games and the trait they implement
+mod practice; // Practice puzzles for playing offline
+mod prefetch; // Caches ranges of puzzles for playing offline
+mod puzzle; // Typed puzzle data from the NYT API
 mod state; // Load/saves the state
+mod stats; // Statistics of finished games
 mod tabs; // Tabs for my game selection method
+mod words; // Word lists for Wordle
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let args = cli::Args::parse();
+
+    // a broken config is reported, rather than silently ignored like the state
+
+    let config = config::load()?;
+
+    words::init(&config.word_lists)?;
+
     let mut app = App::default();
 
     // we must load the state. If there is any errors loading the state, we ignore them and use the
@@ -35,16 +60,43 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     }
 
-    if app.current_game.0 == 255 {
-        // no game currently
+    app.config = config;
+
+    app.follow_today();
+
+    if let Some(command) = &args.command {
+        // a subcommand doesn't change which puzzle the app opens on next time
+        let (page, date, practice) = (app.page.index, app.date, app.practice);
+
+        args.apply(&mut app);
+
+        let result = cli::run(command, &mut app);
+
+        app.page.index = page;
+        app.date = date;
+        app.practice = practice;
+
+        // kept for anything the subcommand downloaded
+        if let Ok(d) = state::save(app) {
+            let _ = std::fs::write(state_loc, d);
+        }
+
+        match result {
+            Ok(text) => println!("{}", text),
+            Err(e) => {
+                eprintln!("{}", e);
 
-        if let Ok(d) = app.download() {
-            app.current_game = d;
+                std::process::exit(1);
+            }
         }
 
-        app.generate_game_string();
+        return Ok(());
     }
 
+    args.apply(&mut app);
+
+    app.load_game();
+
     // loaded the state
 
     enable_raw_mode()?;
@@ -57,43 +109,73 @@ fn main() -> Result<(), Box<dyn Error>> {
     // Run the app
 
     loop {
+        app.poll();
+
         terminal.draw(|frame| app::draw(frame, &mut app))?;
 
         if event::poll(std::time::Duration::from_secs_f32(0.05))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Left => {
-                            app.left();
-                        }
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Left => app.arrow(Arrow::Left),
+
+                    KeyCode::Right => app.arrow(Arrow::Right),
 
-                        KeyCode::Right => app.right(),
+                    KeyCode::Up => app.arrow(Arrow::Up),
 
-                        KeyCode::Up => app.up(),
+                    KeyCode::Down => app.arrow(Arrow::Down),
 
-                        KeyCode::Down => app.down(),
+                    KeyCode::Tab => app.right(),
 
-                        KeyCode::Char('~') => {
-                            app.quit();
-                        }
+                    KeyCode::BackTab => app.left(),
 
-                        KeyCode::Char(char) => {
-                            app.key(char);
-                        }
+                    KeyCode::PageUp => app.up(),
 
-                        KeyCode::Backspace => {
-                            if !app.guess_buffer.is_empty() {
-                                app.guess_buffer.remove(app.guess_buffer.len() - 1);
-                            }
-                        }
+                    KeyCode::PageDown => app.down(),
 
-                        KeyCode::Enter => {
-                            app.enter();
-                        }
+                    KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.toggle_practice();
+                    }
+
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.share();
+                    }
+
+                    KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.prefetch();
+                    }
 
-                        _ => (),
+                    KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.go_today();
                     }
+
+                    KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.toggle_calendar();
+                    }
+
+                    KeyCode::Esc => app.cancel(),
+
+                    KeyCode::Char('~') => {
+                        app.quit();
+                    }
+
+                    KeyCode::Char(char) => {
+                        app.key(char);
+                    }
+
+                    KeyCode::Backspace => app.backspace(),
+
+                    KeyCode::Enter => {
+                        app.enter();
+                    }
+
+                    _ => (),
+                },
+
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                    app.click(mouse.column, mouse.row);
                 }
+
+                _ => (),
             }
         }
 
@@ -110,11 +192,29 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     app.should_quit = false;
 
+    let shared = std::mem::take(&mut app.shared);
+    let share_file = app.config.share.file.take();
+
     let data = state::save(app);
 
     if let Ok(d) = data {
         let _ = std::fs::write(state_loc, d);
     }
 
+    // shared results are written once the terminal is back to normal, so they can be copied
+
+    if !shared.is_empty() {
+        let text = shared.join("\n\n") + "\n";
+
+        match share_file {
+            Some(path) => {
+                let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+                file.write_all(text.as_bytes())?;
+            }
+            None => print!("{}", text),
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,50 @@
+//! Practice puzzles, for playing when the NYT can't be reached. Games that can make their own
+//! puzzles generate them from a seeded RNG, and the others are read from local JSON files in the
+//! NYT schema, in `<dir>/<endpoint>/*.json`.
+
+use crate::config::PracticeConfig;
+use crate::games::GameInfo;
+use crate::puzzle::PuzzleError;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::path::PathBuf;
+
+pub fn dir(config: &PracticeConfig) -> PathBuf {
+    config
+        .dir
+        .clone()
+        .unwrap_or_else(|| crate::state::config_dir().join("practice"))
+}
+
+/// The raw puzzle data for a practice game. The same seed always gives the same puzzle.
+pub fn puzzle(info: &GameInfo, config: &PracticeConfig, seed: u64) -> Result<String, PuzzleError> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    if let Some(generate) = info.generate {
+        return generate(&mut rng);
+    }
+
+    let dir = dir(config).join(info.endpoint);
+
+    // sorted, so the seed picks the same file every time
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    files.sort();
+
+    let file = files.choose(&mut rng).ok_or_else(|| {
+        PuzzleError::NoPractice(format!(
+            "No practice {} puzzles in {}",
+            info.name,
+            dir.display()
+        ))
+    })?;
+
+    std::fs::read_to_string(file)
+        .map_err(|e| PuzzleError::NoPractice(format!("{}: {}", file.display(), e)))
+}
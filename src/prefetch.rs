@@ -0,0 +1,141 @@
+//! Downloads a range of puzzles into the cache, so the archive can be played offline.
+
+use crate::download::{self, Request, Response};
+use crate::games::GAMES;
+use crate::puzzle::PuzzleError;
+use chrono::NaiveDate;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// Downloads each request in turn, waiting `delay` between requests so the API isn't hammered.
+/// Stops early if `done` returns false.
+pub fn run(
+    requests: Vec<Request>,
+    timeout: Duration,
+    delay: Duration,
+    mut done: impl FnMut(Response) -> bool,
+) {
+    let client = download::client(timeout);
+
+    for (i, request) in requests.into_iter().enumerate() {
+        if i > 0 {
+            std::thread::sleep(delay);
+        }
+
+        let result = download::get(&client, &request.url);
+
+        if !done(Response { request, result }) {
+            return;
+        }
+    }
+}
+
+/// What a prefetch did with each puzzle
+#[derive(Default)]
+pub struct Report {
+    pub total: usize,
+    pub cached: usize, // already in the cache, so not downloaded
+    pub downloaded: usize,
+    pub missing: Vec<(NaiveDate, u8)>, // dates the API has no puzzle for, with the game
+    pub outside: Vec<(NaiveDate, u8)>, // dates before the game's first puzzle or after today
+    pub failed: Vec<(NaiveDate, u8, String)>,
+}
+
+impl Report {
+    pub fn new(total: usize, requests: usize, outside: Vec<(NaiveDate, u8)>) -> Self {
+        Self {
+            total,
+            cached: total - requests - outside.len(),
+            outside,
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&mut self, request: &Request, result: Result<(), PuzzleError>) {
+        let date = request.date.date_naive();
+
+        match result {
+            Ok(()) => self.downloaded += 1,
+            Err(PuzzleError::NotFound) => self.missing.push((date, request.game)),
+            Err(e) => self.failed.push((date, request.game, e.to_string())),
+        }
+    }
+
+    /// A one line summary, for the app
+    pub fn summary(&self) -> String {
+        format!(
+            "Prefetched {}/{}: {} downloaded, {} already cached, {} not available, {} outside the archive, {} failed",
+            self.total - self.remaining(),
+            self.total,
+            self.downloaded,
+            self.cached,
+            self.missing.len(),
+            self.outside.len(),
+            self.failed.len()
+        )
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.total
+            - self.cached
+            - self.downloaded
+            - self.missing.len()
+            - self.outside.len()
+            - self.failed.len()
+    }
+
+    /// The summary followed by every puzzle that couldn't be downloaded, for the subcommand
+    pub fn details(&self) -> String {
+        let mut lines = vec![self.summary()];
+
+        for (date, game) in &self.missing {
+            lines.push(format!(
+                "{} {}: not available",
+                date, GAMES[*game as usize].endpoint
+            ));
+        }
+
+        for (date, game) in &self.outside {
+            lines.push(format!(
+                "{} {}: outside the archive",
+                date, GAMES[*game as usize].endpoint
+            ));
+        }
+
+        for (date, game, error) in &self.failed {
+            lines.push(format!(
+                "{} {}: {}",
+                date, GAMES[*game as usize].endpoint, error
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// A prefetch running on a background thread for the app
+pub struct Background {
+    responses: Receiver<Response>,
+    pub report: Report,
+}
+
+impl Background {
+    pub fn start(
+        requests: Vec<Request>,
+        report: Report,
+        timeout: Duration,
+        delay: Duration,
+    ) -> Self {
+        let (tx, responses) = mpsc::channel();
+
+        // stops once the app drops the receiver, e.g. when the prefetch is cancelled
+        std::thread::spawn(move || run(requests, timeout, delay, |r| tx.send(r).is_ok()));
+
+        Self { responses, report }
+    }
+
+    /// The prefetch downloads finished since the last call, still to be cached
+    pub fn finished(&self) -> Vec<Response> {
+        download::finished(&self.responses)
+    }
+}
@@ -0,0 +1,280 @@
+//! Typed models of the puzzles served by the NYT games API.
+//! Each puzzle is deserialized and then validated, so a change in the NYT schema shows up as an
+//! error instead of an empty or broken game.
+
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Details about a puzzle that aren't needed to play it. Practice puzzles may have none.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct Metadata {
+    pub number: Option<u64>, // the number everyone's results are posted under
+    pub print_date: Option<String>,
+    pub editor: Option<String>,
+    pub constructors: Option<String>,
+}
+
+impl Metadata {
+    /// Names the puzzle in shared results: by `label` if there is one, otherwise by its number
+    /// as formatted by `number`, falling back to its print date
+    pub fn name(&self, label: Option<&str>, number: impl Fn(u64) -> String) -> String {
+        match (label, self.number) {
+            (Some(label), _) => label.to_string(),
+            (None, Some(n)) => number(n),
+            (None, None) => self.print_date.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct WordlePuzzle {
+    pub solution: String,
+
+    // metadata, so optional
+    #[serde(default)]
+    pub days_since_launch: Option<u64>,
+    #[serde(default)]
+    pub print_date: Option<String>,
+    #[serde(default)]
+    pub editor: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ConnectionsCard {
+    pub content: String,
+    #[serde(default)]
+    pub position: u8,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ConnectionsCategory {
+    pub title: String,
+    pub cards: Vec<ConnectionsCard>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ConnectionsPuzzle {
+    pub categories: Vec<ConnectionsCategory>,
+
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub print_date: Option<String>,
+    #[serde(default)]
+    pub editor: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StrandsPuzzle {
+    pub clue: String,
+    pub spangram: String,
+    pub theme_words: Vec<String>,
+    pub starting_board: Vec<String>,
+    pub theme_coords: HashMap<String, Vec<[usize; 2]>>, // [row, column] of each letter
+    pub spangram_coords: Vec<[usize; 2]>,
+
+    pub solutions: Vec<String>, // every dictionary word that can be traced on the board
+
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub print_date: Option<String>,
+    #[serde(default)]
+    pub editor: Option<String>,
+    #[serde(default)]
+    pub constructors: Option<String>,
+}
+
+impl WordlePuzzle {
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            number: self.days_since_launch,
+            print_date: self.print_date.clone(),
+            editor: self.editor.clone(),
+            constructors: None,
+        }
+    }
+}
+
+impl ConnectionsPuzzle {
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            number: self.id,
+            print_date: self.print_date.clone(),
+            editor: self.editor.clone(),
+            constructors: None,
+        }
+    }
+}
+
+impl StrandsPuzzle {
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            number: self.id,
+            print_date: self.print_date.clone(),
+            editor: self.editor.clone(),
+            constructors: self.constructors.clone(),
+        }
+    }
+
+    /// The letter at `[row, column]`
+    pub fn letter(&self, [row, col]: [usize; 2]) -> Option<char> {
+        self.starting_board.get(row)?.chars().nth(col)
+    }
+
+    /// The word spelt out by a path of cells
+    pub fn spell(&self, path: &[[usize; 2]]) -> String {
+        path.iter().filter_map(|&cell| self.letter(cell)).collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    Network(String),                  // the request could not be made
+    NotFound, // the API has no puzzle for the date, or answered `status: ERROR`
+    Json(serde_json::Error), // the data did not match the expected schema
+    Invalid(String), // the data parsed, but does not make a playable puzzle
+    NoPractice(String), // no practice puzzle could be made
+    OutOfRange(NaiveDate, NaiveDate), // the date is outside the first and latest puzzle dates
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::Network(e) => write!(f, "Failed to download game: {}", e),
+            PuzzleError::NotFound => write!(f, "No puzzle available for this date"),
+            PuzzleError::Json(e) => write!(f, "Failed to read puzzle data: {}", e),
+            PuzzleError::Invalid(e) => write!(f, "Invalid puzzle data: {}", e),
+            PuzzleError::NoPractice(e) => write!(f, "{}", e),
+            PuzzleError::OutOfRange(first, latest) => write!(
+                f,
+                "No puzzle on this date, puzzles run from {} to {}",
+                first, latest
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+impl From<serde_json::Error> for PuzzleError {
+    fn from(e: serde_json::Error) -> Self {
+        PuzzleError::Json(e)
+    }
+}
+
+/// Checks that deserialized puzzle data is playable
+pub trait Validate {
+    fn validate(&self) -> Result<(), PuzzleError>;
+}
+
+/// Parses and validates the raw JSON returned by the API
+pub fn parse<T: serde::de::DeserializeOwned + Validate>(data: &str) -> Result<T, PuzzleError> {
+    let json: serde_json::Value = serde_json::from_str(data)?;
+
+    if json["status"].as_str().unwrap_or("OK") == "ERROR" {
+        return Err(PuzzleError::NotFound);
+    }
+
+    let puzzle: T = serde_json::from_value(json)?;
+
+    puzzle.validate()?;
+
+    Ok(puzzle)
+}
+
+impl Validate for WordlePuzzle {
+    fn validate(&self) -> Result<(), PuzzleError> {
+        if self.solution.chars().count() != 5 || !self.solution.chars().all(char::is_alphabetic) {
+            return Err(PuzzleError::Invalid(format!(
+                "Wordle solution {:?} is not a five letter word",
+                self.solution
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for ConnectionsPuzzle {
+    fn validate(&self) -> Result<(), PuzzleError> {
+        if self.categories.len() != 4 {
+            return Err(PuzzleError::Invalid(format!(
+                "expected 4 Connections categories, found {}",
+                self.categories.len()
+            )));
+        }
+
+        for cat in &self.categories {
+            if cat.cards.len() != 4 {
+                return Err(PuzzleError::Invalid(format!(
+                    "category {:?} has {} cards instead of 4",
+                    cat.title,
+                    cat.cards.len()
+                )));
+            }
+
+            if cat.cards.iter().any(|c| c.content.is_empty()) {
+                return Err(PuzzleError::Invalid(format!(
+                    "category {:?} has an empty card",
+                    cat.title
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for StrandsPuzzle {
+    fn validate(&self) -> Result<(), PuzzleError> {
+        let width = self.starting_board.first().map(|r| r.len()).unwrap_or(0);
+
+        if width == 0 || self.starting_board.iter().any(|r| r.len() != width) {
+            return Err(PuzzleError::Invalid(
+                "Strands board is empty or not rectangular".to_string(),
+            ));
+        }
+
+        if self.spangram.is_empty() || self.theme_words.is_empty() {
+            return Err(PuzzleError::Invalid(
+                "Strands puzzle has no theme words".to_string(),
+            ));
+        }
+
+        // without it every other word is rejected, so hints could never be earned
+        if self.solutions.is_empty() {
+            return Err(PuzzleError::Invalid(
+                "Strands puzzle has no word list".to_string(),
+            ));
+        }
+
+        // every word must be traced on the board by its coordinates
+        let mut words = vec![(&self.spangram, &self.spangram_coords)];
+
+        for word in &self.theme_words {
+            match self.theme_coords.get(word) {
+                Some(coords) => words.push((word, coords)),
+                None => {
+                    return Err(PuzzleError::Invalid(format!(
+                        "Strands theme word {:?} has no coordinates",
+                        word
+                    )))
+                }
+            }
+        }
+
+        for (word, coords) in words {
+            if !self.spell(coords).eq_ignore_ascii_case(word) {
+                return Err(PuzzleError::Invalid(format!(
+                    "Strands word {:?} does not match its coordinates on the board",
+                    word
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
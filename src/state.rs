@@ -1,18 +1,28 @@
-use crate::app::App;
+use crate::app::{self, App};
 
 pub fn load(data: String) -> Result<App, serde_json::Error> {
-    let app: App = serde_json::from_str(&data)?;
+    let mut app: App = serde_json::from_str(&data)?;
+
+    // the tabs always come from the registered games, not from the saved state
+    app.page.values = app::tab_names();
+
+    if app.page.index as usize >= app.page.values.len() {
+        app.page.index = 0;
+    }
 
     Ok(app)
 }
 
-pub fn save(app: App) -> Result<String, serde_json::Error> {
+pub fn save(mut app: App) -> Result<String, serde_json::Error> {
+    app.store_progress();
+
     let data = serde_json::to_string_pretty(&app)?;
 
     Ok(data)
 }
 
-pub fn get_loc() -> String {
+/// The directory the state and config are kept in
+pub fn config_dir() -> std::path::PathBuf {
     let home = std::path::PathBuf::from(
         std::env::var("HOME").expect("This program currently doesn't work on Windows"),
     );
@@ -23,5 +33,12 @@ pub fn get_loc() -> String {
         let _ = std::fs::create_dir_all(&config_dir);
     }
 
-    config_dir.join("state.json").to_string_lossy().to_string()
+    config_dir
+}
+
+pub fn get_loc() -> String {
+    config_dir()
+        .join("state.json")
+        .to_string_lossy()
+        .to_string()
 }
@@ -0,0 +1,245 @@
+//! Statistics of finished daily games. Practice games are never recorded.
+
+use crate::games::{Outcome, GAMES};
+use chrono::{Duration, NaiveDate};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{BarChart, Block, Paragraph},
+    Frame,
+};
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct Record {
+    pub game: String,    // the game's endpoint, e.g. "wordle"
+    pub date: NaiveDate, // the puzzle date, not when it was played
+    #[serde(default)]
+    pub number: Option<u64>, // the puzzle number, if the puzzle has one
+    pub won: bool,
+    pub guesses: usize,
+    pub mistakes: usize,
+    pub hints: usize,
+    pub seconds: u64, // time spent playing
+}
+
+impl Record {
+    pub fn new(
+        game: &str,
+        date: NaiveDate,
+        number: Option<u64>,
+        outcome: Outcome,
+        seconds: u64,
+    ) -> Self {
+        Self {
+            game: game.to_string(),
+            date,
+            number,
+            won: outcome.won,
+            guesses: outcome.guesses,
+            mistakes: outcome.mistakes,
+            hints: outcome.hints,
+            seconds,
+        }
+    }
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Summary {
+    pub played: usize,
+    pub won: usize,
+    pub current_streak: usize,
+    pub max_streak: usize,
+}
+
+impl Summary {
+    pub fn win_percent(&self) -> usize {
+        (self.won * 100).checked_div(self.played).unwrap_or(0)
+    }
+}
+
+/// Summarises the records of one game. Streaks count wins on consecutive puzzle dates, so
+/// playing an old puzzle late still extends the streak it belongs to.
+pub fn summary(records: &[Record], game: &str) -> Summary {
+    let mut records: Vec<&Record> = records.iter().filter(|r| r.game == game).collect();
+
+    records.sort_by_key(|r| r.date);
+
+    let mut summary = Summary {
+        played: records.len(),
+        won: records.iter().filter(|r| r.won).count(),
+        ..Default::default()
+    };
+
+    let mut streak = 0;
+    let mut last: Option<NaiveDate> = None;
+
+    for record in records {
+        if !record.won {
+            streak = 0;
+        } else if last.is_some_and(|d| d + Duration::days(1) == record.date) {
+            streak += 1;
+        } else {
+            streak = 1;
+        }
+
+        last = Some(record.date);
+
+        summary.max_streak = summary.max_streak.max(streak);
+    }
+
+    summary.current_streak = streak;
+
+    summary
+}
+
+/// How many Wordle games were won in each number of guesses
+pub fn distribution(records: &[Record]) -> [u64; 6] {
+    let mut counts = [0; 6];
+
+    for record in records {
+        if record.game == "wordle" && record.won && (1..=6).contains(&record.guesses) {
+            counts[record.guesses - 1] += 1;
+        }
+    }
+
+    counts
+}
+
+/// One line summarising each game
+fn summary_lines(records: &[Record]) -> Vec<String> {
+    GAMES
+        .iter()
+        .map(|info| {
+            let summary = summary(records, info.endpoint);
+
+            format!(
+                "{:<12} played {:>4}   win % {:>3}   current streak {:>3}   max streak {:>3}",
+                info.name,
+                summary.played,
+                summary.win_percent(),
+                summary.current_streak,
+                summary.max_streak
+            )
+        })
+        .collect()
+}
+
+/// The stats page as plain text, for printing
+pub fn report(records: &[Record]) -> String {
+    let mut lines = summary_lines(records);
+
+    lines.push(String::new());
+    lines.push("Wordle guess distribution".to_string());
+
+    for (i, count) in distribution(records).iter().enumerate() {
+        lines.push(format!("{}: {}", i + 1, count));
+    }
+
+    lines.join("\n")
+}
+
+pub fn draw(frame: &mut Frame, area: Rect, records: &[Record]) {
+    let mut text: Vec<Line> = vec!["Statistics of daily games".into(), "".into()];
+
+    text.extend(summary_lines(records).into_iter().map(Line::from));
+
+    let [summaries, chart] = Layout::vertical([
+        Constraint::Length(text.len() as u16 + 1),
+        Constraint::Min(0),
+    ])
+    .areas(area);
+
+    frame.render_widget(Paragraph::new(text), summaries);
+
+    let counts = distribution(records);
+    let labels = ["1", "2", "3", "4", "5", "6"];
+
+    let data: Vec<(&str, u64)> = labels.iter().copied().zip(counts).collect();
+
+    frame.render_widget(
+        BarChart::default()
+            .block(Block::bordered().title("Wordle guess distribution"))
+            .data(&data)
+            .bar_width(5)
+            .bar_gap(2)
+            .bar_style(Style::default().fg(Color::Green))
+            .value_style(Style::default().fg(Color::Black).bg(Color::Green)),
+        chart,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Wordle record for day `day` of September 2024
+    fn wordle(day: u32, won: bool) -> Record {
+        let outcome = Outcome {
+            won,
+            guesses: 4,
+            mistakes: 0,
+            hints: 0,
+        };
+
+        Record::new(
+            "wordle",
+            NaiveDate::from_ymd_opt(2024, 9, day).unwrap(),
+            None,
+            outcome,
+            60,
+        )
+    }
+
+    #[test]
+    fn a_gap_between_dates_ends_the_streak() {
+        let records = [
+            wordle(1, true),
+            wordle(2, true),
+            wordle(3, true),
+            wordle(5, true),
+        ];
+
+        assert_eq!(
+            summary(&records, "wordle"),
+            Summary {
+                played: 4,
+                won: 4,
+                current_streak: 1,
+                max_streak: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn a_loss_ends_the_streak() {
+        let mut records = vec![wordle(1, true), wordle(2, true), wordle(3, false)];
+
+        assert_eq!(summary(&records, "wordle").current_streak, 0);
+        assert_eq!(summary(&records, "wordle").max_streak, 2);
+
+        records.push(wordle(4, true));
+
+        assert_eq!(summary(&records, "wordle").current_streak, 1);
+        assert_eq!(summary(&records, "wordle").win_percent(), 75);
+    }
+
+    #[test]
+    fn streaks_follow_puzzle_dates_not_play_order() {
+        // the 2nd was played last, joining the 1st and 3rd into one streak
+        let mut records = vec![wordle(1, true), wordle(3, true), wordle(2, true)];
+
+        // other games don't count
+        records.push(Record {
+            game: "strands".to_string(),
+            ..wordle(4, true)
+        });
+
+        let summary = summary(&records, "wordle");
+
+        assert_eq!(
+            (summary.played, summary.current_streak, summary.max_streak),
+            (3, 3, 3)
+        );
+    }
+}
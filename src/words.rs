@@ -0,0 +1,94 @@
+//! Word lists for Wordle: the guesses that are accepted, and the answers practice puzzles are
+//! picked from. The bundled list is used unless the config adds to or replaces it.
+
+use crate::config::WordListConfig;
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::Path;
+use std::sync::OnceLock;
+
+static WORDS: &str = include_str!("../assets/wordle.txt");
+
+static WORD_LIST: OnceLock<WordList> = OnceLock::new();
+
+pub struct WordList {
+    pub guesses: HashSet<String>,
+    pub answers: Vec<String>,
+}
+
+/// Reads a word list with one word per line. Words are lowercased, and anything that isn't a
+/// five letter word is skipped.
+fn parse(data: &str) -> Vec<String> {
+    data.lines()
+        .map(|x| x.trim().to_lowercase())
+        .filter(|x| x.chars().count() == 5 && x.chars().all(char::is_alphabetic))
+        .collect()
+}
+
+fn read(path: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let data = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(parse(&data))
+}
+
+impl WordList {
+    pub fn bundled() -> Self {
+        let words = parse(WORDS);
+
+        Self {
+            guesses: words.iter().cloned().collect(),
+            answers: words,
+        }
+    }
+
+    pub fn from_config(config: &WordListConfig) -> Result<Self, Box<dyn Error>> {
+        let mut list = if config.replace_guesses {
+            Self {
+                guesses: HashSet::new(),
+                answers: Vec::new(),
+            }
+        } else {
+            Self::bundled()
+        };
+
+        for path in &config.guesses {
+            list.guesses.extend(read(path)?);
+        }
+
+        match &config.answers {
+            Some(path) => list.answers = read(path)?,
+            None if config.replace_guesses => {
+                list.answers = list.guesses.iter().cloned().collect();
+
+                // sorted, so a seeded pick of an answer is repeatable
+                list.answers.sort();
+            }
+            None => (),
+        }
+
+        // an answer is always a valid guess
+        list.guesses.extend(list.answers.iter().cloned());
+
+        if list.guesses.is_empty() {
+            return Err("the configured word lists have no five letter words".into());
+        }
+
+        Ok(list)
+    }
+}
+
+/// Loads the word lists from the config. Must be called before the word lists are used, or the
+/// bundled list is used instead.
+pub fn init(config: &WordListConfig) -> Result<(), Box<dyn Error>> {
+    let _ = WORD_LIST.set(WordList::from_config(config)?);
+
+    Ok(())
+}
+
+pub fn get() -> &'static WordList {
+    WORD_LIST.get_or_init(WordList::bundled)
+}
+
+pub fn is_guess(word: &str) -> bool {
+    get().guesses.contains(word)
+}
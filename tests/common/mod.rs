@@ -0,0 +1,106 @@
+//! A local stand-in for the NYT API, serving the puzzles in `tests/fixtures`, and helpers to run
+//! the app against it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub struct Server {
+    pub url: String,
+    requests: Arc<AtomicUsize>,
+}
+
+impl Server {
+    /// Starts serving `tests/fixtures` on a free port. A path without a fixture is a 404.
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut request = String::new();
+                let _ = BufReader::new(&stream).read_line(&mut request);
+
+                // "GET /wordle/v2/2024-09-30.json HTTP/1.1"
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                let response = match std::fs::read_to_string(fixtures.join(&path[1..])) {
+                    Ok(body) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    Err(_) => {
+                        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// How many requests have been answered so far
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+/// An empty home directory for one test, so the real state and config are never touched
+pub fn home(name: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("nytg_cli_{}_{}", std::process::id(), name));
+
+    let _ = std::fs::remove_dir_all(&home);
+    std::fs::create_dir_all(&home).unwrap();
+
+    home
+}
+
+/// Writes the config file in `home`
+pub fn config(home: &Path, config: &str) {
+    let config_dir = home.join(".config/nytg_cli");
+
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.json"), config).unwrap();
+}
+
+/// Runs the app with `args`, downloading from `base_url`
+pub fn run(home: &Path, base_url: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_nytg_cli"));
+
+    command
+        .args(args)
+        .env("HOME", home)
+        .env_remove("NYTG_BASE_URL");
+
+    if let Some(url) = base_url {
+        command.env("NYTG_BASE_URL", url);
+    }
+
+    command.output().unwrap()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).to_string()
+}
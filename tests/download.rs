@@ -0,0 +1,239 @@
+//! Downloads puzzles from a local stand-in for the NYT API, through the subcommands.
+
+mod common;
+
+use common::{config, home, run, stderr, stdout, Server};
+
+#[test]
+fn solves_each_game() {
+    let server = Server::start();
+    let home = home("solves_each_game");
+
+    let solve = |game| {
+        let output = run(
+            &home,
+            Some(&server.url),
+            &["solve", "--game", game, "--date", "2024-09-30"],
+        );
+
+        assert!(output.status.success(), "{}", stderr(&output));
+
+        stdout(&output)
+    };
+
+    assert_eq!(solve("wordle"), "CRANE\n");
+    assert!(solve("connections").contains("FISH: BASS, COD, EEL, PIKE"));
+    assert!(solve("strands").contains("Spangram: ABCD"));
+}
+
+#[test]
+fn fetch_prints_the_puzzle_and_caches_it() {
+    let server = Server::start();
+    let home = home("fetch_caches");
+
+    let args = ["fetch", "--game", "wordle", "--date", "2024-09-30"];
+
+    let first = run(&home, Some(&server.url), &args);
+    let second = run(&home, Some(&server.url), &args);
+
+    assert!(first.status.success(), "{}", stderr(&first));
+    assert!(stdout(&first).contains("\"solution\": \"crane\""));
+    assert_eq!(stdout(&first), stdout(&second));
+
+    // the second fetch is read from the saved state
+    assert_eq!(server.requests(), 1);
+}
+
+#[test]
+fn status_error_is_not_found() {
+    let server = Server::start();
+    let home = home("status_error");
+
+    let output = run(
+        &home,
+        Some(&server.url),
+        &["fetch", "--game", "wordle", "--date", "2024-10-01"],
+    );
+
+    assert!(!output.status.success());
+    assert_eq!(stderr(&output), "No puzzle available for this date\n");
+}
+
+#[test]
+fn bad_json_is_reported() {
+    let server = Server::start();
+    let home = home("bad_json");
+
+    let args = ["fetch", "--game", "wordle", "--date", "2024-10-02"];
+
+    let output = run(&home, Some(&server.url), &args);
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).starts_with("Failed to read puzzle data"));
+
+    // bad data isn't cached, so it is downloaded again
+    run(&home, Some(&server.url), &args);
+
+    assert_eq!(server.requests(), 2);
+}
+
+#[test]
+fn missing_puzzle_is_not_found() {
+    let server = Server::start();
+    let home = home("missing_puzzle");
+
+    let output = run(
+        &home,
+        Some(&server.url),
+        &["fetch", "--game", "strands", "--date", "2024-10-01"],
+    );
+
+    assert!(!output.status.success());
+    assert_eq!(stderr(&output), "No puzzle available for this date\n");
+}
+
+#[test]
+fn base_url_can_come_from_the_config() {
+    let server = Server::start();
+    let home = home("base_url_config");
+
+    config(
+        &home,
+        &format!(r#"{{ "api": {{ "base_url": "{}/" }} }}"#, server.url),
+    );
+
+    let output = run(
+        &home,
+        None,
+        &["solve", "--game", "wordle", "--date", "2024-09-30"],
+    );
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "CRANE\n");
+}
+
+#[test]
+fn prefetch_reports_what_it_could_not_download() {
+    let server = Server::start();
+    let home = home("prefetch");
+
+    config(&home, r#"{ "prefetch": { "delay_ms": 0 } }"#);
+
+    let args = ["prefetch", "2024-09-30", "2024-10-02"];
+
+    let output = run(&home, Some(&server.url), &args);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let report = stdout(&output);
+
+    assert!(report
+        .starts_with("Prefetched 9/9: 3 downloaded, 0 already cached, 5 not available, 0 outside the archive, 1 failed"));
+    assert!(report.contains("2024-10-01 wordle: not available"));
+    assert!(report.contains("2024-10-02 strands: not available"));
+    assert!(report.contains("2024-10-02 wordle: Failed to read puzzle data"));
+
+    // only the puzzles that weren't cached are downloaded again
+    let output = run(&home, Some(&server.url), &args);
+
+    assert!(stdout(&output).starts_with("Prefetched 9/9: 0 downloaded, 3 already cached"));
+    assert_eq!(server.requests(), 9 + 6);
+}
+
+#[test]
+fn prefetch_reports_dates_outside_the_archive() {
+    let server = Server::start();
+    let home = home("prefetch_outside");
+
+    config(&home, r#"{ "prefetch": { "delay_ms": 0 } }"#);
+
+    let prefetch = |from, to| {
+        let output = run(&home, Some(&server.url), &["prefetch", from, to]);
+
+        assert!(output.status.success(), "{}", stderr(&output));
+
+        stdout(&output)
+    };
+
+    // strands starts the day after
+    let report = prefetch("2024-03-03", "2024-03-03");
+
+    assert!(report.starts_with(
+        "Prefetched 3/3: 0 downloaded, 0 already cached, 2 not available, 1 outside the archive, 0 failed"
+    ));
+    assert!(report.contains("2024-03-03 strands: outside the archive"));
+
+    // nothing has been released yet, so nothing is downloaded
+    let report = prefetch("2999-01-01", "2999-01-02");
+
+    assert!(report.starts_with(
+        "Prefetched 6/6: 0 downloaded, 0 already cached, 0 not available, 6 outside the archive, 0 failed"
+    ));
+    assert!(report.contains("2999-01-02 wordle: outside the archive"));
+
+    assert_eq!(server.requests(), 2);
+}
+
+#[test]
+fn dates_outside_the_archive_are_not_downloaded() {
+    let server = Server::start();
+    let home = home("outside_archive");
+
+    for date in ["2024-03-03", "2999-01-01"] {
+        let output = run(
+            &home,
+            Some(&server.url),
+            &["fetch", "--game", "strands", "--date", date],
+        );
+
+        assert!(!output.status.success());
+        assert!(
+            stderr(&output).starts_with("No puzzle on this date, puzzles run from 2024-03-04 to")
+        );
+    }
+
+    assert_eq!(server.requests(), 0);
+}
+
+#[test]
+fn share_names_puzzles_by_number() {
+    let server = Server::start();
+    let home = home("share");
+
+    let state = r#"{
+        "practice": false,
+        "progress": {
+            "wordle/2024-09-30": { "guesses": ["slate", "crane"], "complete": true, "won": true, "hard_mode": true },
+            "connections/2024-09-30": { "guesses": [["ASH", "BASS", "COD", "EEL"], ["BASS", "COD", "EEL", "PIKE"]] },
+            "strands/2024-09-30": { "found_words": ["EFGH", "ABCD", "LKJI"], "hints_at": [1], "complete": true }
+        }
+    }"#;
+
+    std::fs::create_dir_all(home.join(".config/nytg_cli")).unwrap();
+    std::fs::write(home.join(".config/nytg_cli/state.json"), state).unwrap();
+
+    let share = |game| {
+        let output = run(
+            &home,
+            Some(&server.url),
+            &["share", "--game", game, "--date", "2024-09-30"],
+        );
+
+        (output.status.success(), stdout(&output))
+    };
+
+    assert_eq!(
+        share("wordle"),
+        (
+            true,
+            "Wordle 1,199 2/6*\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩\n".to_string()
+        )
+    );
+    assert_eq!(
+        share("strands"),
+        (true, "Strands #200\n“Test”\n🔵💡🟡🔵\n".to_string())
+    );
+
+    // connections isn't finished, so there is nothing to share
+    assert!(!share("connections").0);
+}